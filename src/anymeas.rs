@@ -0,0 +1,154 @@
+use core::fmt::Debug;
+
+use embedded_hal::{delay::DelayNs, digital, i2c::I2c};
+
+use crate::{
+    error::SensorError,
+    register::{AnyMeas, Bank0},
+    Error, FeedState, Module, Pinnacle, PositionReportingMode, STATUS1_SW_CC,
+};
+
+/// AnyMeas mode flag in the SYS_CONFIG1 register
+const SYS_CONFIG1_ANYMEAS: u8 = 0b0000_1000;
+/// Starts a measurement while in AnyMeas mode
+const SYS_CONFIG1_ANYMEAS_START: u8 = 0b0001_0000;
+
+/// Registers that are shared between normal operation and AnyMeas mode
+const SHARED_REGISTERS: [AnyMeas; 5] = [
+    AnyMeas::ADC_CONFIG1,
+    AnyMeas::BIT_LENGTH,
+    AnyMeas::MUX_CONTROL,
+    AnyMeas::ADC_CONFIG2,
+    AnyMeas::APERTURE_WIDTH,
+];
+
+/// Settings of a single AnyMeas (raw ADC) measurement.
+///
+/// Values are written to the AnyMeas registers as is, see Cirque's AnyMeas documentation
+/// for their meaning.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnyMeasConfig {
+    /// ADC gain and electrode frequency
+    pub adc_config: u8,
+    /// Number of samples to accumulate, in units of 128
+    pub bit_length: u8,
+    /// ADC multiplexer selection
+    pub mux_control: u8,
+    /// Additional ADC configuration
+    pub adc_config2: u8,
+    /// Aperture width, in units of 125ns
+    pub aperture_width: u8,
+    /// Electrodes toggled during the measurement
+    pub toggle: u32,
+    /// Polarity of the toggled electrodes
+    pub polarity: u32,
+}
+
+impl Default for AnyMeasConfig {
+    fn default() -> Self {
+        Self {
+            adc_config: 0x00,
+            bit_length: 4,
+            mux_control: 0x01,
+            adc_config2: 0x00,
+            aperture_width: 4,
+            toggle: 0x0000_FFFF,
+            polarity: 0x0000_0000,
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Run a single AnyMeas (raw ADC) measurement and return the result
+    /// The touchpad is switched to AnyMeas mode for the measurement and its previous
+    /// configuration is restored afterwards.
+    pub fn anymeas_measure(
+        &mut self,
+        config: &AnyMeasConfig,
+        delay: &mut impl DelayNs,
+    ) -> Result<i16, Error<E, PinError>> {
+        let sys_config = self.read_reg(&Bank0::SYS_CONFIG1)?;
+        let mut saved = [0u8; SHARED_REGISTERS.len()];
+        for (value, reg) in saved.iter_mut().zip(SHARED_REGISTERS) {
+            *value = self.read_reg(&reg)?;
+        }
+
+        self.write_reg(&Bank0::SYS_CONFIG1, SYS_CONFIG1_ANYMEAS)?;
+        let result = self.run_anymeas(config, delay);
+        let restored = self.restore_anymeas(sys_config, saved);
+
+        // The measurement error takes priority over a restore error it may have caused
+        let value = result?;
+        restored?;
+
+        Ok(value)
+    }
+
+    /// Leave AnyMeas mode, restoring the configuration saved before the measurement
+    fn restore_anymeas(
+        &mut self,
+        sys_config: u8,
+        saved: [u8; SHARED_REGISTERS.len()],
+    ) -> Result<(), Error<E, PinError>> {
+        self.write_reg(&Bank0::SYS_CONFIG1, sys_config)?;
+        for (value, reg) in saved.into_iter().zip(SHARED_REGISTERS) {
+            self.write_reg(&reg, value)?;
+        }
+
+        self.clear_flags()
+    }
+
+    fn run_anymeas(
+        &mut self,
+        config: &AnyMeasConfig,
+        delay: &mut impl DelayNs,
+    ) -> Result<i16, Error<E, PinError>> {
+        self.write_reg(&AnyMeas::ADC_CONFIG1, config.adc_config)?;
+        self.write_reg(&AnyMeas::BIT_LENGTH, config.bit_length)?;
+        self.write_reg(&AnyMeas::MUX_CONTROL, config.mux_control)?;
+        self.write_reg(&AnyMeas::ADC_CONFIG2, config.adc_config2)?;
+        self.write_reg(&AnyMeas::APERTURE_WIDTH, config.aperture_width)?;
+
+        let toggle = config.toggle.to_be_bytes();
+        let polarity = config.polarity.to_be_bytes();
+        for (reg, value) in [
+            (AnyMeas::TOGGLE0, toggle[0]),
+            (AnyMeas::TOGGLE1, toggle[1]),
+            (AnyMeas::TOGGLE2, toggle[2]),
+            (AnyMeas::TOGGLE3, toggle[3]),
+            (AnyMeas::POLARITY0, polarity[0]),
+            (AnyMeas::POLARITY1, polarity[1]),
+            (AnyMeas::POLARITY2, polarity[2]),
+            (AnyMeas::POLARITY3, polarity[3]),
+        ] {
+            self.write_reg(&reg, value)?;
+        }
+
+        self.clear_flags()?;
+        self.write_reg(
+            &Bank0::SYS_CONFIG1,
+            SYS_CONFIG1_ANYMEAS | SYS_CONFIG1_ANYMEAS_START,
+        )?;
+
+        let mut elapsed_ms = 0;
+        while self.read_reg(&Bank0::STATUS1)? & STATUS1_SW_CC == 0 {
+            if elapsed_ms >= self.bus.timings.measurement_timeout_ms {
+                return Err(Error::SensorError(SensorError::MeasurementTimeout));
+            }
+            delay.delay_ms(1);
+            elapsed_ms += 1;
+        }
+
+        let high = self.read_reg(&AnyMeas::RESULT_HIGH)?;
+        let low = self.read_reg(&AnyMeas::RESULT_LOW)?;
+
+        Ok(i16::from_be_bytes([high, low]))
+    }
+}
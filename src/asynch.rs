@@ -0,0 +1,369 @@
+//! Async low-level interface.
+//!
+//! [`Pinnacle`] is the async counterpart of [`ll::Pinnacle`](crate::ll::Pinnacle), giving
+//! typed access to the bank 0 registers and reading reports over an `embedded-hal-async` bus.
+//!
+//! Operations made of several bus transactions, like read-modify-write sequences or reading
+//! a report and clearing the flags, can yield to the executor between the transactions, see
+//! `set_cooperative()`. On slow 100 kHz buses this keeps high-priority tasks (e.g. USB) from
+//! being blocked for the whole sequence.
+//!
+//! Transactions are neither retried nor verified, and positions are reported as decoded.
+//! `run()` processes them like the blocking driver: absolute positions are clamped to the usable
+//! area and follow the mounting orientation, and reports are numbered in `sequence`.
+//!
+//! [`Pinnacle::run()`] is a ready-made task owning the touchpad, reading reports whenever the
+//! data ready line is asserted and sending them to a channel, e.g. of `embassy_sync`:
+//!
+//! ```rust,ignore
+//! static EVENTS: Channel<CriticalSectionRawMutex, Event, 8> = Channel::new();
+//!
+//! struct Events(Sender<'static, CriticalSectionRawMutex, Event, 8>);
+//!
+//! impl EventSender for Events {
+//!     async fn send(&mut self, event: Event) {
+//!         self.0.send(event).await
+//!     }
+//! }
+//!
+//! #[embassy_executor::task]
+//! async fn touchpad(pinnacle: Pinnacle<I2c<'static, Async>>, data_ready: Input<'static>) {
+//!     let mut data_ready = data_ready;
+//!     let error = pinnacle
+//!         .run(PositionMode::Absolute, &mut data_ready, &mut Events(EVENTS.sender()))
+//!         .await;
+//!     defmt::error!("touchpad failed: {}", error);
+//! }
+//! ```
+
+use core::{
+    convert::Infallible,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use embedded_hal_async::{digital::Wait, i2c::I2c};
+
+use crate::{
+    config::Mask,
+    ll::{FeedConfig1, FeedConfig2, Field, Readable, Writable, R, W},
+    packet::{AbsolutePacket, RelativePacket},
+    AbsoluteData, Error, Event, FeedMode, I2cAddress, MountingOrientation, PositionMode,
+    RelativeData, RelativeEvent, XYInverted, XYSwapped, STATUS1_SW_DR,
+};
+
+/// Address of the STATUS1 register
+const STATUS1: u8 = 0x02;
+/// Address of the first packet byte
+const PACKET_BYTE0: u8 = 0x12;
+/// Times `run()` re-reads STATUS1 while the data ready line stays high without a report, before
+/// only waiting for its next rising edge
+const MISSED_REPORT_RETRIES: u8 = 3;
+
+/// Future yielding to the executor once
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+
+        Poll::Pending
+    }
+}
+
+/// Receives the events of `Pinnacle::run()`, e.g. the sender of a channel
+pub trait EventSender {
+    /// Send an event, waiting while the channel is full
+    fn send(&mut self, event: Event) -> impl Future<Output = ()>;
+}
+
+/// Raw async register access to a Pinnacle ASIC on an I²C bus
+pub struct Pinnacle<I2C> {
+    i2c: I2C,
+    address: I2cAddress,
+    cooperative: bool,
+    orientation: MountingOrientation,
+    sequence: u32,
+}
+
+impl<I2C, E> Pinnacle<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a low-level interface to the touchpad at `address`, without yielding between
+    /// transactions
+    pub fn new(i2c: I2C, address: impl Into<I2cAddress>) -> Self {
+        Self {
+            i2c,
+            address: address.into(),
+            cooperative: false,
+            orientation: MountingOrientation::Normal,
+            sequence: 0,
+        }
+    }
+
+    /// Return the underlying I2C instance for reuse
+    pub fn free(self) -> I2C {
+        self.i2c
+    }
+
+    /// Get the I²C address of the touchpad
+    pub fn address(&self) -> I2cAddress {
+        self.address
+    }
+
+    /// Enable/disable yielding to the executor between the transactions of multi-transaction
+    /// operations
+    pub fn set_cooperative(&mut self, cooperative: bool) {
+        self.cooperative = cooperative;
+    }
+
+    /// Set the mounting orientation applied by `run()`, in hardware in relative mode and in
+    /// software in absolute mode like `Tm040040::set_orientation()`
+    pub fn set_orientation(&mut self, orientation: MountingOrientation) {
+        self.orientation = orientation;
+    }
+
+    /// Get the mounting orientation applied by `run()`
+    pub fn orientation(&self) -> MountingOrientation {
+        self.orientation
+    }
+
+    /// Number a report sent by `run()`
+    fn next_sequence(&mut self) -> u32 {
+        let sequence = self.sequence;
+        self.sequence = self.sequence.wrapping_add(1);
+
+        sequence
+    }
+
+    /// Yield to the executor if cooperative
+    async fn yield_point(&self) {
+        if self.cooperative {
+            YieldNow { yielded: false }.await;
+        }
+    }
+
+    /// Read consecutive registers, starting at `addr`
+    pub async fn read_raw(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), E> {
+        self.i2c
+            .write_read(self.address.get(), &[addr | Mask::Read as u8], buffer)
+            .await
+    }
+
+    /// Write the register at `addr`
+    pub async fn write_raw(&mut self, addr: u8, value: u8) -> Result<(), E> {
+        self.i2c
+            .write(self.address.get(), &[addr | Mask::Write as u8, value])
+            .await
+    }
+
+    /// Read the register `REG`
+    pub async fn read<REG: Readable>(&mut self) -> Result<R<REG>, E> {
+        let mut buffer = [0u8];
+        self.read_raw(REG::ADDR, &mut buffer).await?;
+
+        Ok(R::new(buffer[0]))
+    }
+
+    /// Write the register `REG`, starting from all bits cleared
+    pub async fn write<REG: Writable>(
+        &mut self,
+        f: impl FnOnce(&mut W<REG>) -> &mut W<REG>,
+    ) -> Result<(), E> {
+        let mut w = W::new(0);
+        f(&mut w);
+
+        self.write_raw(REG::ADDR, w.value()).await
+    }
+
+    /// Read, modify and write back the register `REG`, yielding in between if cooperative
+    pub async fn modify<REG: Readable + Writable>(
+        &mut self,
+        f: impl for<'w> FnOnce(&R<REG>, &'w mut W<REG>) -> &'w mut W<REG>,
+    ) -> Result<(), E> {
+        let r = self.read::<REG>().await?;
+        let mut w = W::new(r.bits());
+        f(&r, &mut w);
+        self.yield_point().await;
+
+        self.write_raw(REG::ADDR, w.value()).await
+    }
+
+    /// Set a field of the register `REG`, keeping all other bits
+    pub async fn set_field<REG: Readable + Writable, F: Field<REG>>(
+        &mut self,
+        field: F,
+    ) -> Result<(), E> {
+        self.modify::<REG>(|_, w| w.set(field)).await
+    }
+
+    /// Read the pending packet if the software data ready flag is set, and clear the flags
+    async fn read_packet(&mut self, buffer: &mut [u8]) -> Result<bool, E> {
+        let mut status = [0u8];
+        self.read_raw(STATUS1, &mut status).await?;
+        if status[0] & STATUS1_SW_DR == 0 {
+            return Ok(false);
+        }
+        self.yield_point().await;
+        self.read_raw(PACKET_BYTE0, buffer).await?;
+        self.yield_point().await;
+        self.write_raw(STATUS1, 0x00).await?;
+
+        Ok(true)
+    }
+
+    /// Read a report in absolute mode, `None` if no report is pending
+    pub async fn absolute_data(&mut self) -> Result<Option<AbsoluteData>, E> {
+        let mut packet = [0u8; 6];
+        if !self.read_packet(&mut packet).await? {
+            return Ok(None);
+        }
+        let AbsolutePacket {
+            button_state,
+            x_pos,
+            y_pos,
+            z_level,
+        } = AbsolutePacket::decode(packet);
+
+        Ok(Some(AbsoluteData {
+            button_state,
+            ..AbsoluteData::new(x_pos, y_pos, z_level)
+        }))
+    }
+
+    /// Read a report in relative mode, `None` if no report is pending
+    /// The wheel counts are read along, they're 0 unless IntelliMouse is enabled.
+    pub async fn relative_data(&mut self) -> Result<Option<RelativeData>, E> {
+        let mut packet = [0u8; 4];
+        if !self.read_packet(&mut packet).await? {
+            return Ok(None);
+        }
+        let RelativePacket {
+            primary_pressed,
+            secondary_pressed,
+            aux_pressed,
+            x_delta,
+            y_delta,
+            wheel,
+            ..
+        } = RelativePacket::decode(packet);
+
+        Ok(Some(RelativeData {
+            primary_pressed,
+            secondary_pressed,
+            aux_pressed,
+            wheel,
+            ..RelativeData::new(x_delta, y_delta)
+        }))
+    }
+
+    /// Run the touchpad as a task: enable feed in `mode`, then wait for the data ready line,
+    /// read each report and send it to `events`, until a bus or pin error occurs.
+    ///
+    /// Absolute positions are clamped to the usable area and oriented, see `set_orientation()`,
+    /// and reports are numbered in `sequence`, like with the blocking driver. Relative reports
+    /// changing the buttons are sent as `RelativeEvent::ButtonPress`, all others as
+    /// `RelativeEvent::Motion`.
+    ///
+    /// A data ready line staying high without a pending report, e.g. one shared with another
+    /// device, is re-checked a few times and then only trusted again on its next rising edge.
+    pub async fn run<DR: Wait>(
+        mut self,
+        mode: PositionMode,
+        data_ready: &mut DR,
+        events: &mut impl EventSender,
+    ) -> Error<E, DR::Error> {
+        match self.run_inner(mode, data_ready, events).await {
+            Ok(never) => match never {},
+            Err(error) => error,
+        }
+    }
+
+    async fn run_inner<DR: Wait>(
+        &mut self,
+        mode: PositionMode,
+        data_ready: &mut DR,
+        events: &mut impl EventSender,
+    ) -> Result<Infallible, Error<E, DR::Error>> {
+        let address = self.address;
+        let bus = |error| Error::BusError { address, error };
+
+        self.set_field::<FeedConfig1, _>(mode).await.map_err(bus)?;
+        // Axes are only swapped and inverted in hardware in relative mode
+        let (swapped, inverted) = match mode {
+            PositionMode::Relative => (self.orientation.swapped(), self.orientation.inverted()),
+            PositionMode::Absolute => (XYSwapped::Normal, XYInverted::Normal),
+        };
+        self.set_field::<FeedConfig2, _>(swapped)
+            .await
+            .map_err(bus)?;
+        self.set_field::<FeedConfig1, _>(inverted)
+            .await
+            .map_err(bus)?;
+        self.set_field::<FeedConfig1, _>(FeedMode::Enabled)
+            .await
+            .map_err(bus)?;
+        self.write_raw(STATUS1, 0x00).await.map_err(bus)?;
+
+        let mut last_buttons = 0;
+        let mut misses = 0;
+        loop {
+            if misses < MISSED_REPORT_RETRIES {
+                data_ready.wait_for_high().await.map_err(Error::PinError)?;
+            } else {
+                data_ready
+                    .wait_for_rising_edge()
+                    .await
+                    .map_err(Error::PinError)?;
+            }
+
+            let event = match mode {
+                PositionMode::Absolute => {
+                    let Some(data) = self.absolute_data().await.map_err(bus)? else {
+                        misses += 1;
+                        YieldNow { yielded: false }.await;
+                        continue;
+                    };
+                    let (x_pos, y_pos) = self.orientation.apply_absolute(data.x_pos, data.y_pos);
+                    Event::Absolute(AbsoluteData {
+                        x_pos,
+                        y_pos,
+                        sequence: self.next_sequence(),
+                        ..data
+                    })
+                }
+                PositionMode::Relative => {
+                    let Some(data) = self.relative_data().await.map_err(bus)? else {
+                        misses += 1;
+                        YieldNow { yielded: false }.await;
+                        continue;
+                    };
+                    let data = RelativeData {
+                        sequence: self.next_sequence(),
+                        ..data
+                    };
+                    let buttons = data.buttons();
+                    let changed = buttons != last_buttons;
+                    last_buttons = buttons;
+                    Event::Relative(if changed {
+                        RelativeEvent::ButtonPress(data)
+                    } else {
+                        RelativeEvent::Motion(data)
+                    })
+                }
+            };
+            misses = 0;
+            events.send(event).await;
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use core::fmt::Debug;
+
+use embedded_hal::{
+    digital::{self, InputPin},
+    i2c::I2c,
+};
+
+#[cfg(feature = "absolute")]
+use crate::Absolute;
+#[cfg(any(feature = "absolute", feature = "relative"))]
+use crate::FeedEnabled;
+use crate::{
+    Error, FeedMode, I2cAddress, Module, MountingOrientation, NoFeed, Pinnacle, PositionMode,
+    Relative,
+};
+
+/// A driver in the typestate matching the touchpad's current configuration, see
+/// `Tm040040::attach()`
+pub enum Attached<'a, I2C, M, PinError> {
+    /// Relative mode, feed disabled, also returned for a touchpad in relative mode without the
+    /// `relative` feature, to be switched with `enable_absolute()`
+    RelativeNoFeed(Pinnacle<'a, I2C, M, Relative, NoFeed, PinError>),
+    /// Relative mode, feed enabled
+    #[cfg(feature = "relative")]
+    RelativeFeed(Pinnacle<'a, I2C, M, Relative, FeedEnabled, PinError>),
+    /// Absolute mode, feed disabled
+    #[cfg(feature = "absolute")]
+    AbsoluteNoFeed(Pinnacle<'a, I2C, M, Absolute, NoFeed, PinError>),
+    /// Absolute mode, feed enabled
+    #[cfg(feature = "absolute")]
+    AbsoluteFeed(Pinnacle<'a, I2C, M, Absolute, FeedEnabled, PinError>),
+}
+
+impl<'a, I2C, E, M: Module, PinError> Pinnacle<'a, I2C, M, Relative, NoFeed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    /// Create a trackpad instance for a touchpad that is already configured, e.g. after a warm
+    /// restart of the firmware without resetting the touchpad.
+    /// The feed and position mode are read from the touchpad and a driver in the matching
+    /// typestate is returned. In relative mode the IntelliMouse mode is read too, so the wheel
+    /// byte of reports is decoded. A touchpad in a position mode whose feature is disabled returns
+    /// `Error::ModeMismatch`, except that relative mode with feed disabled is the initial state.
+    pub fn attach(
+        i2c: I2C,
+        address: impl Into<I2cAddress>,
+        hardware_data_ready: &'a mut impl InputPin<Error = PinError>,
+    ) -> Result<Attached<'a, I2C, M, PinError>, Error<E, PinError>> {
+        let mut trackpad = Self::new(i2c, address, hardware_data_ready);
+        let feed = trackpad.feed_mode()?;
+        let position = trackpad.position_mode()?;
+        trackpad.xy_enable()?;
+
+        if position == PositionMode::Relative {
+            // Axes are only swapped and inverted in hardware in relative mode
+            trackpad.pipeline.orientation =
+                MountingOrientation::from_flags(trackpad.read_field()?, trackpad.read_field()?);
+            trackpad.shadows.intelli_mouse = trackpad.read_field()?;
+        }
+
+        trackpad.into_attached(position, feed)
+    }
+
+    /// Move the driver into the typestate of `position` and `feed`
+    pub(crate) fn into_attached(
+        self,
+        position: PositionMode,
+        feed: FeedMode,
+    ) -> Result<Attached<'a, I2C, M, PinError>, Error<E, PinError>> {
+        Ok(match (position, feed) {
+            (PositionMode::Relative, FeedMode::NoFeed) => Attached::RelativeNoFeed(self),
+            #[cfg(feature = "relative")]
+            (PositionMode::Relative, FeedMode::Enabled) => {
+                Attached::RelativeFeed(self.into_state())
+            }
+            #[cfg(feature = "absolute")]
+            (PositionMode::Absolute, FeedMode::NoFeed) => {
+                Attached::AbsoluteNoFeed(self.into_state())
+            }
+            #[cfg(feature = "absolute")]
+            (PositionMode::Absolute, FeedMode::Enabled) => {
+                Attached::AbsoluteFeed(self.into_state())
+            }
+            #[cfg(not(all(feature = "absolute", feature = "relative")))]
+            (found, _) => {
+                let expected = match found {
+                    PositionMode::Relative => PositionMode::Absolute,
+                    PositionMode::Absolute => PositionMode::Relative,
+                };
+                return Err(Error::ModeMismatch { expected, found });
+            }
+        })
+    }
+}
@@ -0,0 +1,131 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+#[cfg(any(feature = "absolute", feature = "relative"))]
+use crate::FeedEnabled;
+#[cfg(feature = "absolute")]
+use crate::{Absolute, AbsoluteData};
+use crate::{Clock, Error, FeedState, Module, Pinnacle, PositionReportingMode};
+#[cfg(feature = "relative")]
+use crate::{Relative, RelativeEvent};
+
+/// Result of a time-sliced poll, see `Tm040040::poll_budgeted()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Budgeted<T> {
+    /// A report was read
+    Report(T),
+    /// No report is pending
+    NoData,
+    /// A report is pending, but reading it didn't fit in the budget. It is read first thing
+    /// on the next poll.
+    OutOfTime,
+}
+
+/// Next step of a budgeted poll
+enum Step {
+    Read,
+    NoData,
+    OutOfTime,
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Check for a pending report, deferring the packet read if it won't fit in `max_us`
+    fn budgeted_check(
+        &mut self,
+        max_us: u32,
+        clock: &mut impl Clock,
+    ) -> Result<Step, Error<E, PinError>> {
+        if self.bus.report_confirmed {
+            return Ok(Step::Read);
+        }
+        let start_us = clock.now_us();
+        if !self.data_ready()? {
+            return Ok(Step::NoData);
+        }
+        self.bus.report_confirmed = true;
+        if self.bus.combined_packet.is_some() {
+            // The packet was read along with the status
+            return Ok(Step::Read);
+        }
+
+        // Reading the packet and clearing the flags takes about as long as the status check
+        let elapsed_us = clock.now_us().saturating_sub(start_us);
+        if elapsed_us * 2 > max_us as u64 {
+            return Ok(Step::OutOfTime);
+        }
+
+        Ok(Step::Read)
+    }
+}
+
+#[cfg(feature = "absolute")]
+impl<I2C, E, M: Module, PinError> Pinnacle<'_, I2C, M, Absolute, FeedEnabled, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    /// Read a report, doing at most the work fitting in `max_us`, for super-loop firmware with
+    /// strict per-iteration timing
+    /// If the status check leaves no time to read the packet, the read is deferred to the next
+    /// call, which then skips the status check.
+    pub fn poll_budgeted(
+        &mut self,
+        max_us: u32,
+        clock: &mut impl Clock,
+    ) -> Result<Budgeted<AbsoluteData>, Error<E, PinError>> {
+        match self.budgeted_check(max_us, clock)? {
+            Step::NoData => Ok(Budgeted::NoData),
+            Step::OutOfTime => Ok(Budgeted::OutOfTime),
+            Step::Read => Ok(self
+                .absolute_data()?
+                .map_or(Budgeted::NoData, Budgeted::Report)),
+        }
+    }
+}
+
+#[cfg(feature = "relative")]
+impl<I2C, E, M: Module, PinError> Pinnacle<'_, I2C, M, Relative, FeedEnabled, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    /// Read an event, doing at most the work fitting in `max_us`, for super-loop firmware with
+    /// strict per-iteration timing
+    /// If the status check leaves no time to read the packet, the read is deferred to the next
+    /// call, which then skips the status check.
+    pub fn poll_budgeted(
+        &mut self,
+        max_us: u32,
+        clock: &mut impl Clock,
+    ) -> Result<Budgeted<RelativeEvent>, Error<E, PinError>> {
+        // A motion event split off the previous report doesn't need the bus
+        let split = self
+            .pipeline
+            .button_decoupler
+            .as_ref()
+            .is_some_and(|decoupler| decoupler.has_pending());
+        let step = if split {
+            Step::Read
+        } else {
+            self.budgeted_check(max_us, clock)?
+        };
+
+        match step {
+            Step::NoData => Ok(Budgeted::NoData),
+            Step::OutOfTime => Ok(Budgeted::OutOfTime),
+            Step::Read => Ok(self
+                .relative_event()?
+                .map_or(Budgeted::NoData, Budgeted::Report)),
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use embedded_hal::i2c::{Error, ErrorKind, I2c};
+
+/// Bus communication profile, trading throughput for robustness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum BusProfile {
+    /// Fast bus with short wiring: no retries, no write verification, full bursts
+    #[default]
+    Standard,
+    /// Slow (100 kHz) bus with long wiring, e.g. for panel-mounted touchpads far from the MCU.
+    /// Failed transactions are retried, configuration writes are verified by reading them back
+    /// and registers are read in short bursts.
+    LongCable,
+}
+
+impl BusProfile {
+    /// How often a failed bus transaction is retried
+    pub fn retries(self) -> u8 {
+        match self {
+            Self::Standard => 0,
+            Self::LongCable => 3,
+        }
+    }
+
+    /// Whether configuration writes are read back and compared
+    pub fn verify_writes(self) -> bool {
+        match self {
+            Self::Standard => false,
+            Self::LongCable => true,
+        }
+    }
+
+    /// Maximum number of registers read in a single transaction
+    pub fn max_burst_len(self) -> usize {
+        match self {
+            Self::Standard => 6,
+            Self::LongCable => 2,
+        }
+    }
+}
+
+/// Classify an error of the bus `I`
+pub(crate) fn error_kind<I: I2c>(error: &I::Error) -> ErrorKind {
+    error.kind()
+}
+
+/// Bus error statistics, for flagging degrading connectors before they fail outright, see
+/// `Tm040040::health()`.
+///
+/// The counts cover the lifetime of the driver. To count over the lifetime of the device,
+/// persist them and restore them with `Tm040040::restore_health()` on startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BusHealth {
+    /// Number of bus transactions attempted, including retries
+    pub transactions: u32,
+    /// Number of failed transactions, including ones that succeeded on retry
+    pub errors: u32,
+    /// Number of failed transactions that weren't acknowledged
+    pub nacks: u32,
+    /// Number of retried transactions
+    pub retries: u32,
+    /// Number of transactions that succeeded on retry
+    pub recoveries: u32,
+    /// Number of transactions that failed after all retries
+    pub failures: u32,
+}
+
+impl BusHealth {
+    /// Share of failed transactions in parts per million, `None` if there were none
+    pub fn error_rate_ppm(&self) -> Option<u32> {
+        (self.errors as u64 * 1_000_000)
+            .checked_div(self.transactions as u64)
+            .map(|rate| rate as u32)
+    }
+
+    pub(crate) fn record(&mut self, kind: Option<ErrorKind>, attempt: u8, will_retry: bool) {
+        self.transactions = self.transactions.wrapping_add(1);
+        let Some(kind) = kind else {
+            if attempt > 0 {
+                self.recoveries = self.recoveries.wrapping_add(1);
+            }
+            return;
+        };
+        self.errors = self.errors.wrapping_add(1);
+        if matches!(kind, ErrorKind::NoAcknowledge(_)) {
+            self.nacks = self.nacks.wrapping_add(1);
+        }
+        if will_retry {
+            self.retries = self.retries.wrapping_add(1);
+        } else {
+            self.failures = self.failures.wrapping_add(1);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::RelativeData;
+
+/// A relative report, see `Tm040040::relative_event()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelativeEvent {
+    /// Motion, with the button state unchanged since the previous event unless button
+    /// decoupling is disabled
+    Motion(RelativeData),
+    /// A change of the button state, with zero motion
+    ButtonPress(RelativeData),
+}
+
+impl RelativeEvent {
+    /// The report data of the event
+    pub fn data(&self) -> &RelativeData {
+        match self {
+            Self::Motion(data) | Self::ButtonPress(data) => data,
+        }
+    }
+
+    /// Convert the event into its report data
+    pub fn into_data(self) -> RelativeData {
+        match self {
+            Self::Motion(data) | Self::ButtonPress(data) => data,
+        }
+    }
+}
+
+/// Splits reports with both a button change and motion into a button event followed by a
+/// motion event, so taps detected mid-motion don't move the cursor
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ButtonDecoupler {
+    buttons: (bool, bool, bool),
+    pending: Option<RelativeData>,
+}
+
+impl ButtonDecoupler {
+    /// Whether a motion event split off the previous report is pending
+    pub fn has_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Take the motion event split off the previous report
+    pub fn pending(&mut self) -> Option<RelativeData> {
+        self.pending.take()
+    }
+
+    pub fn split(&mut self, data: RelativeData) -> RelativeEvent {
+        let buttons = (
+            data.primary_pressed,
+            data.secondary_pressed,
+            data.aux_pressed,
+        );
+        if buttons == self.buttons {
+            return RelativeEvent::Motion(data);
+        }
+        self.buttons = buttons;
+
+        if data.x_delta != 0 || data.y_delta != 0 || data.wheel != 0 {
+            self.pending = Some(data);
+        }
+        RelativeEvent::ButtonPress(RelativeData {
+            x_delta: 0,
+            y_delta: 0,
+            wheel: 0,
+            ..data
+        })
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{transform::Keystone, AbsoluteData};
+
+/// Corners touched during a `Calibration`, in order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+impl Corner {
+    const ALL: [Self; 4] = [
+        Self::TopLeft,
+        Self::TopRight,
+        Self::BottomRight,
+        Self::BottomLeft,
+    ];
+}
+
+/// Four point calibration, e.g. for curved overlays distorting positions near the edges.
+///
+/// Prompt the user to touch the corners of the overlay in order, see `next_corner()`, and
+/// feed absolute reports into `update()`. The position of each touch is averaged and
+/// captured when the finger is lifted. Once complete, `keystone()` computes a `Keystone`
+/// transform mapping the touched corners onto the target corners.
+/// Calibrate without a transform set on the driver.
+#[derive(Clone, Debug)]
+pub struct Calibration {
+    touch_z: u8,
+    corners: [(i32, i32); 4],
+    captured: usize,
+    sum: (i64, i64),
+    samples: u32,
+}
+
+impl Calibration {
+    /// New calibration, counting reports with a Z level of at least `touch_z` as touches
+    pub fn new(touch_z: u8) -> Self {
+        Self {
+            touch_z,
+            corners: [(0, 0); 4],
+            captured: 0,
+            sum: (0, 0),
+            samples: 0,
+        }
+    }
+
+    /// The corner to touch next, or `None` if the calibration is complete
+    pub fn next_corner(&self) -> Option<Corner> {
+        Corner::ALL.get(self.captured).copied()
+    }
+
+    /// Whether all corners were captured
+    pub fn is_complete(&self) -> bool {
+        self.captured == Corner::ALL.len()
+    }
+
+    /// Process an absolute report, returning the corner captured by it, if any
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<Corner> {
+        let corner = self.next_corner()?;
+        if data.z_level >= self.touch_z {
+            self.sum.0 += data.x_pos as i64;
+            self.sum.1 += data.y_pos as i64;
+            self.samples += 1;
+            return None;
+        }
+        if self.samples == 0 {
+            return None;
+        }
+
+        let samples = self.samples as i64;
+        self.corners[self.captured] =
+            ((self.sum.0 / samples) as i32, (self.sum.1 / samples) as i32);
+        self.captured += 1;
+        self.sum = (0, 0);
+        self.samples = 0;
+
+        Some(corner)
+    }
+
+    /// The captured corner positions, ordered like `Corner`
+    pub fn corners(&self) -> Option<[(i32, i32); 4]> {
+        self.is_complete().then_some(self.corners)
+    }
+
+    /// Transform mapping the captured corners onto `target`, ordered like `Corner`
+    /// Returns `None` if the calibration is incomplete or the captured corners are degenerate.
+    pub fn keystone(&self, target: [(i32, i32); 4]) -> Option<Keystone> {
+        Keystone::from_corners(self.corners()?, target)
+    }
+
+    /// Restart the calibration
+    pub fn reset(&mut self) {
+        *self = Self::new(self.touch_z);
+    }
+}
@@ -0,0 +1,34 @@
+/// A monotonic time source, used for time-based features like report age tracking.
+///
+/// Implemented for closures returning the current time, so e.g. a HAL timer can be used
+/// with `&mut || timer.now().as_micros()`.
+pub trait Clock {
+    /// Current time in microseconds since an arbitrary fixed point
+    fn now_us(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Clock for F {
+    fn now_us(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// What to do with reports older than the configured maximum age.
+///
+/// The touchpad holds a report until the flags are cleared, so with infrequent polling a
+/// report can be much older than the sample rate suggests. The age is estimated as the
+/// time since the flags were last cleared.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum StaleReportPolicy {
+    /// Return the report with its `stale` flag set
+    #[default]
+    Tag,
+    /// Drop the report and clear the flags, so a fresh report is generated
+    Discard,
+}
+
+/// Estimated age of a pending report
+pub(crate) struct ReportAge {
+    pub age_us: Option<u32>,
+    pub stale: bool,
+}
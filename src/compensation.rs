@@ -0,0 +1,284 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    era::Registers, register::Bank0, Error, FeedState, Module, Pinnacle, PositionReportingMode,
+    CAL_CONFIG1_CALIBRATE,
+};
+
+/// ADC attenuation bits in the TRACK_ADC_CONFIG extended register
+pub(crate) const TRACK_ADC_CONFIG_ATTENUATION: u8 = 0b1100_0000;
+/// Compensation enable bits in the CAL_CONFIG1 register
+const CAL_CONFIG1_BACKGROUND_COMP: u8 = 0b0000_0010;
+const CAL_CONFIG1_NERD_COMP: u8 = 0b0000_0100;
+const CAL_CONFIG1_TRACK_ERROR_COMP: u8 = 0b0000_1000;
+const CAL_CONFIG1_TAP_COMP: u8 = 0b0001_0000;
+const CAL_CONFIG1_COMP: u8 = CAL_CONFIG1_BACKGROUND_COMP
+    | CAL_CONFIG1_NERD_COMP
+    | CAL_CONFIG1_TRACK_ERROR_COMP
+    | CAL_CONFIG1_TAP_COMP;
+
+/// An external temperature source, used for temperature compensation.
+///
+/// Implemented for closures returning the current temperature in °C.
+pub trait TemperatureSource {
+    /// Current temperature in °C
+    fn temperature_c(&mut self) -> i8;
+}
+
+impl<F: FnMut() -> i8> TemperatureSource for F {
+    fn temperature_c(&mut self) -> i8 {
+        self()
+    }
+}
+
+/// Attenuation of the tracking ADC, higher attenuation reduces sensitivity
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum AdcAttenuation {
+    /// No attenuation, highest sensitivity
+    #[default]
+    X1 = 0b0000_0000,
+    /// Attenuation by a factor of 2
+    X2 = 0b0100_0000,
+    /// Attenuation by a factor of 3
+    X3 = 0b1000_0000,
+    /// Attenuation by a factor of 4, lowest sensitivity
+    X4 = 0b1100_0000,
+}
+
+impl AdcAttenuation {
+    fn from_step(step: i16) -> Self {
+        match step {
+            i16::MIN..=0 => Self::X1,
+            1 => Self::X2,
+            2 => Self::X3,
+            _ => Self::X4,
+        }
+    }
+
+    fn step(self) -> i16 {
+        match self {
+            Self::X1 => 0,
+            Self::X2 => 1,
+            Self::X3 => 2,
+            Self::X4 => 3,
+        }
+    }
+
+    fn from_reg(value: u8) -> Self {
+        Self::from_step(((value & TRACK_ADC_CONFIG_ATTENUATION) >> 6) as i16)
+    }
+}
+
+/// Compensation mechanisms of the touchpad, enabled in the CAL_CONFIG1 register
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompensationFlags {
+    /// Background compensation, following slow drift of the baseline
+    pub background: bool,
+    /// Noise compensation (NERD)
+    pub nerd: bool,
+    /// Track error compensation
+    pub track_error: bool,
+    /// Tap compensation
+    pub tap: bool,
+}
+
+impl CompensationFlags {
+    /// All compensation mechanisms enabled
+    pub const ALL: Self = Self {
+        background: true,
+        nerd: true,
+        track_error: true,
+        tap: true,
+    };
+
+    fn from_reg(value: u8) -> Self {
+        Self {
+            background: value & CAL_CONFIG1_BACKGROUND_COMP != 0,
+            nerd: value & CAL_CONFIG1_NERD_COMP != 0,
+            track_error: value & CAL_CONFIG1_TRACK_ERROR_COMP != 0,
+            tap: value & CAL_CONFIG1_TAP_COMP != 0,
+        }
+    }
+
+    fn bits(self) -> u8 {
+        let flag = |enabled: bool, bit: u8| if enabled { bit } else { 0 };
+
+        flag(self.background, CAL_CONFIG1_BACKGROUND_COMP)
+            | flag(self.nerd, CAL_CONFIG1_NERD_COMP)
+            | flag(self.track_error, CAL_CONFIG1_TRACK_ERROR_COMP)
+            | flag(self.tap, CAL_CONFIG1_TAP_COMP)
+    }
+}
+
+/// Sensitivity settings recomputed for a temperature
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    /// Attenuation of the tracking ADC
+    pub adc_attenuation: AdcAttenuation,
+    /// Minimum Z level of wide touches on both axes
+    pub wide_z_min: u8,
+}
+
+/// Linear temperature compensation of the touchpad sensitivity, for installations where the Z
+/// baseline drifts with temperature.
+///
+/// The settings tuned at the reference temperature are adjusted proportionally to the
+/// difference to the current temperature.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TemperatureCompensation {
+    /// Temperature the settings were tuned at, in °C
+    pub reference_c: i8,
+    /// Settings at the reference temperature
+    pub reference: Thresholds,
+    /// Change of the wide Z minimum per 10°C above the reference temperature
+    pub wide_z_min_per_10c: i8,
+    /// Temperature change (in °C) raising the ADC attenuation by one step, negative values
+    /// lower it with rising temperature and 0 keeps it fixed
+    pub attenuation_step_c: i8,
+}
+
+impl Default for TemperatureCompensation {
+    fn default() -> Self {
+        Self {
+            reference_c: 25,
+            reference: Thresholds {
+                adc_attenuation: AdcAttenuation::X1,
+                wide_z_min: 0x04,
+            },
+            wide_z_min_per_10c: 0,
+            attenuation_step_c: 0,
+        }
+    }
+}
+
+impl TemperatureCompensation {
+    /// Recompute the settings for `temperature_c`
+    pub fn thresholds(&self, temperature_c: i8) -> Thresholds {
+        let difference = temperature_c as i16 - self.reference_c as i16;
+
+        let wide_z_min =
+            self.reference.wide_z_min as i16 + difference * self.wide_z_min_per_10c as i16 / 10;
+        let steps = difference
+            .checked_div(self.attenuation_step_c as i16)
+            .unwrap_or(0);
+
+        Thresholds {
+            adc_attenuation: AdcAttenuation::from_step(
+                self.reference.adc_attenuation.step() + steps,
+            ),
+            wide_z_min: wide_z_min.clamp(0, u8::MAX as i16) as u8,
+        }
+    }
+}
+
+/// Applies temperature compensation, only writing the touchpad when settings change
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct TemperatureCompensator {
+    pub config: TemperatureCompensation,
+    pub applied: Option<Thresholds>,
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Get the attenuation of the tracking ADC
+    pub fn adc_attenuation(&mut self) -> Result<AdcAttenuation, Error<E, PinError>> {
+        let value = self.read_era_register(Registers::TRACK_ADC_CONFIG)?;
+
+        Ok(AdcAttenuation::from_reg(value))
+    }
+
+    /// Set the attenuation of the tracking ADC
+    pub fn set_adc_attenuation(
+        &mut self,
+        attenuation: AdcAttenuation,
+    ) -> Result<(), Error<E, PinError>> {
+        self.era_batch(|pad| {
+            let value = pad.read_era_register(Registers::TRACK_ADC_CONFIG)?;
+            let value = (value & !TRACK_ADC_CONFIG_ATTENUATION) | attenuation as u8;
+
+            pad.write_era_register(Registers::TRACK_ADC_CONFIG, value)
+        })
+    }
+
+    /// Get the enabled compensation mechanisms
+    pub fn compensation_flags(&mut self) -> Result<CompensationFlags, Error<E, PinError>> {
+        let value = self.read_reg(&Bank0::CAL_CONFIG1)?;
+
+        Ok(CompensationFlags::from_reg(value))
+    }
+
+    /// Enable/disable compensation mechanisms
+    /// They take effect with the next calibration.
+    pub fn set_compensation_flags(
+        &mut self,
+        flags: CompensationFlags,
+    ) -> Result<(), Error<E, PinError>> {
+        // Leave the calibration trigger clear
+        let value =
+            self.read_reg(&Bank0::CAL_CONFIG1)? & !(CAL_CONFIG1_COMP | CAL_CONFIG1_CALIBRATE);
+
+        self.write_reg(&Bank0::CAL_CONFIG1, value | flags.bits())
+    }
+
+    /// Set the minimum Z level of wide touches on both axes
+    pub fn set_wide_z_min(&mut self, wide_z_min: u8) -> Result<(), Error<E, PinError>> {
+        self.era_batch(|pad| {
+            pad.write_era_register(Registers::X_AXIS_WIDE_Z_MIN, wide_z_min)?;
+            pad.write_era_register(Registers::Y_AXIS_WIDE_Z_MIN, wide_z_min)
+        })
+    }
+
+    /// Enable temperature compensation, `None` to disable
+    /// Settings are only changed by `compensate_temperature()` and `apply_temperature()`.
+    pub fn set_temperature_compensation(&mut self, config: Option<TemperatureCompensation>) {
+        self.pipeline.temperature_compensation = config.map(|config| TemperatureCompensator {
+            config,
+            applied: None,
+        });
+    }
+
+    /// Read the temperature source and apply the compensated settings, see `apply_temperature()`
+    /// Returns `None` if no temperature source is set or compensation is disabled.
+    pub fn compensate_temperature(&mut self) -> Result<Option<Thresholds>, Error<E, PinError>> {
+        let Some(source) = self.hooks.temperature_source.as_mut() else {
+            return Ok(None);
+        };
+        let temperature_c = source.temperature_c();
+
+        self.apply_temperature(temperature_c)
+    }
+
+    /// Apply the settings compensated for `temperature_c`
+    /// The touchpad is only written if the settings changed since they were last applied.
+    /// Returns `None` if compensation is disabled.
+    pub fn apply_temperature(
+        &mut self,
+        temperature_c: i8,
+    ) -> Result<Option<Thresholds>, Error<E, PinError>> {
+        let Some(compensator) = self.pipeline.temperature_compensation else {
+            return Ok(None);
+        };
+        let thresholds = compensator.config.thresholds(temperature_c);
+
+        let applied = compensator.applied;
+        if applied.map(|a| a.adc_attenuation) != Some(thresholds.adc_attenuation) {
+            self.set_adc_attenuation(thresholds.adc_attenuation)?;
+        }
+        if applied.map(|a| a.wide_z_min) != Some(thresholds.wide_z_min) {
+            self.set_wide_z_min(thresholds.wide_z_min)?;
+        }
+        if let Some(compensator) = self.pipeline.temperature_compensation.as_mut() {
+            compensator.applied = Some(thresholds);
+        }
+
+        Ok(Some(thresholds))
+    }
+}
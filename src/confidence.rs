@@ -0,0 +1,22 @@
+/// Z level of a solid touch, scoring full confidence
+const SOLID_Z: u32 = 32;
+/// Movement between reports (or away from the smoothed position) halving the confidence, in
+/// counts
+const HALF_MOVEMENT: u32 = 64;
+
+/// Confidence in a touch in percent, from its Z level, its movement since the previous report
+/// and how far smoothing moved it
+pub(crate) fn confidence(z_level: u8, jump: u16, residual: u16, multi_finger: bool) -> u8 {
+    if z_level == 0 {
+        return 0;
+    }
+    let z_score = (z_level as u32 * 100 / SOLID_Z).min(100);
+    let movement = jump.max(residual) as u32;
+    let score = z_score * HALF_MOVEMENT / (HALF_MOVEMENT + movement);
+
+    if multi_finger {
+        (score / 2) as u8
+    } else {
+        score as u8
+    }
+}
@@ -3,12 +3,17 @@ use crate::{
     register::{Bank0, Register},
 };
 
-pub(crate) trait Bitfield {
+pub(crate) trait Bitfield: TryFrom<u8, Error = SensorError> {
     const BITMASK: u8;
     type Reg: Register;
     const REGISTER: Self::Reg;
 
     fn bits(self) -> u8;
+
+    /// Extract the field from a raw register value
+    fn from_reg(value: u8) -> Result<Self, SensorError> {
+        Self::try_from((value & Self::BITMASK) >> Self::BITMASK.trailing_zeros())
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -18,15 +23,54 @@ pub enum Mask {
 }
 
 /// i2c adress
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum Address {
     #[default]
     Primary = 0x2a,
     Secondary = 0x2c,
 }
 
+/// A validated 7-bit I²C address, either one of the known `Address`es or a custom one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct I2cAddress(u8);
+
+impl I2cAddress {
+    /// Create an address, checking that it's a 7-bit address outside the reserved ranges
+    /// (0x00-0x07 and 0x78-0x7f)
+    pub const fn new(address: u8) -> Result<Self, SensorError> {
+        match address {
+            0x08..=0x77 => Ok(Self(address)),
+            _ => Err(SensorError::InvalidAddress(address)),
+        }
+    }
+
+    /// The raw 7-bit address
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for I2cAddress {
+    fn default() -> Self {
+        Address::default().into()
+    }
+}
+
+impl From<Address> for I2cAddress {
+    fn from(address: Address) -> Self {
+        Self(address as u8)
+    }
+}
+
+impl TryFrom<u8> for I2cAddress {
+    type Error = SensorError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
 /// Touchpad power modes
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum PowerMode {
     /// Shutdown touchpad. Consumes very low power, does not track touch
     Shutdown = 1,
@@ -59,7 +103,7 @@ impl TryFrom<u8> for PowerMode {
 
 /// Feed mode controls if position reporting is turned on or not.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum FeedMode {
     /// Report finger tracking
     #[default]
@@ -88,7 +132,7 @@ impl TryFrom<u8> for FeedMode {
 
 /// Position reporting mode
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum PositionMode {
     /// Relative mode reports position deltas. Relative mode also allows using internal tap detection, scroll detection and extended features (controlled by other flags).
     #[default]
@@ -118,7 +162,7 @@ impl TryFrom<u8> for PositionMode {
 
 /// Enable or disable hardware filters. Cirque does not reccommend disabling filters.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum FilterMode {
     #[default]
     Enable = 0,
@@ -145,7 +189,7 @@ impl TryFrom<u8> for FilterMode {
 
 /// Disable specific axis.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum XYEnable {
     #[default]
     Enabled = 0,
@@ -161,6 +205,17 @@ impl Bitfield for XYEnable {
         (self as u8) << 3
     }
 }
+impl XYEnable {
+    /// Whether the X axis is enabled
+    pub fn x_enabled(self) -> bool {
+        matches!(self, Self::Enabled | Self::YDisabled)
+    }
+
+    /// Whether the Y axis is enabled
+    pub fn y_enabled(self) -> bool {
+        matches!(self, Self::Enabled | Self::XDisabled)
+    }
+}
 impl TryFrom<u8> for XYEnable {
     type Error = SensorError;
     fn try_from(value: u8) -> Result<Self, Self::Error> {
@@ -176,7 +231,7 @@ impl TryFrom<u8> for XYEnable {
 
 /// Invert axis reporting (flips sign).
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum XYInverted {
     #[default]
     Normal = 0,
@@ -207,7 +262,7 @@ impl TryFrom<u8> for XYInverted {
 
 /// Intelli mouse mode controlls scroll reporting.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum IntelliMouseMode {
     #[default]
     Disabled = 0,
@@ -233,8 +288,12 @@ impl TryFrom<u8> for IntelliMouseMode {
 }
 
 /// Handle what types of taps are detected by hardware.
+///
+/// This is the only documented control over hardware taps: the timing and thresholds of tap
+/// detection are not exposed through documented extended registers. Where the hardware tap
+/// doesn't feel right, disable it and detect taps in software, e.g. with a `GestureEngine`.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum TapMode {
     /// Detect all kinds of taps
     #[default]
@@ -243,9 +302,11 @@ pub enum TapMode {
     AllTapsDisable = 1,
     /// Dont detect secondary button taps. Secondary taps are taps in the upper right corner of the touchpad
     SecondaryTapDisable = 2,
+    /// Both disable bits set, no taps are detected like with `AllTapsDisable`
+    AllAndSecondaryTapDisable = 3,
 }
 impl Bitfield for TapMode {
-    const BITMASK: u8 = 0b0000_0010;
+    const BITMASK: u8 = 0b0000_0110;
     type Reg = Bank0;
     const REGISTER: Self::Reg = Self::Reg::FEED_CONFIG2;
     fn bits(self) -> u8 {
@@ -259,6 +320,7 @@ impl TryFrom<u8> for TapMode {
             0 => Ok(Self::Enabled),
             1 => Ok(Self::AllTapsDisable),
             2 => Ok(Self::SecondaryTapDisable),
+            3 => Ok(Self::AllAndSecondaryTapDisable),
             _ => Err(SensorError::InvalidDiscriminant),
         }
     }
@@ -266,7 +328,7 @@ impl TryFrom<u8> for TapMode {
 
 /// Control scroll mode. Cirque docs don't say what this actually does.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum ScrollMode {
     #[default]
     Enabled = 0,
@@ -293,7 +355,7 @@ impl TryFrom<u8> for ScrollMode {
 
 /// Control glide extend mode. In glide extend mode, drag actions can be extended by lifting the finger when an edge is reached and repositioning the finger.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum GlideExtendMode {
     #[default]
     Enabled = 0,
@@ -320,7 +382,7 @@ impl TryFrom<u8> for GlideExtendMode {
 
 /// Swap X and Y axis.
 #[allow(dead_code)]
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
 pub enum XYSwapped {
     #[default]
     Normal = 0,
@@ -329,7 +391,7 @@ pub enum XYSwapped {
 impl Bitfield for XYSwapped {
     const BITMASK: u8 = 0b1000_0000;
     type Reg = Bank0;
-    const REGISTER: Self::Reg = Self::Reg::FEED_CONFIG1;
+    const REGISTER: Self::Reg = Self::Reg::FEED_CONFIG2;
     fn bits(self) -> u8 {
         (self as u8) << 7
     }
@@ -344,3 +406,118 @@ impl TryFrom<u8> for XYSwapped {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::{
+        Bitfield, FeedMode, FilterMode, GlideExtendMode, IntelliMouseMode, PositionMode, PowerMode,
+        ScrollMode, TapMode, XYEnable, XYInverted, XYSwapped,
+    };
+    use crate::register::{Bank0, Register};
+
+    /// Documented setting bits of the registers modelled by `Bitfield`s, from the Pinnacle
+    /// ASIC documentation. The reset bit of SYS_CONFIG1 is a command rather than a setting.
+    const DOCUMENTED: [(&str, Bank0, u8); 3] = [
+        ("SYS_CONFIG1", Bank0::SYS_CONFIG1, 0b0000_0110),
+        ("FEED_CONFIG1", Bank0::FEED_CONFIG1, 0b1101_1111),
+        ("FEED_CONFIG2", Bank0::FEED_CONFIG2, 0b1001_1111),
+    ];
+
+    struct Field {
+        name: &'static str,
+        addr: u8,
+        mask: u8,
+    }
+
+    fn field<B: Bitfield>(name: &'static str) -> Field {
+        Field {
+            name,
+            addr: B::REGISTER.addr(),
+            mask: B::BITMASK,
+        }
+    }
+
+    fn fields() -> [Field; 11] {
+        [
+            field::<PowerMode>("PowerMode"),
+            field::<FeedMode>("FeedMode"),
+            field::<PositionMode>("PositionMode"),
+            field::<FilterMode>("FilterMode"),
+            field::<XYEnable>("XYEnable"),
+            field::<XYInverted>("XYInverted"),
+            field::<IntelliMouseMode>("IntelliMouseMode"),
+            field::<TapMode>("TapMode"),
+            field::<ScrollMode>("ScrollMode"),
+            field::<GlideExtendMode>("GlideExtendMode"),
+            field::<XYSwapped>("XYSwapped"),
+        ]
+    }
+
+    #[test]
+    fn masks_dont_overlap() {
+        let fields = fields();
+        for (i, a) in fields.iter().enumerate() {
+            for b in &fields[i + 1..] {
+                assert!(
+                    a.addr != b.addr || a.mask & b.mask == 0,
+                    "{} overlaps {}",
+                    a.name,
+                    b.name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn documented_bits_are_covered_once() {
+        let fields = fields();
+        for field in &fields {
+            assert!(
+                DOCUMENTED
+                    .iter()
+                    .any(|(_, reg, bits)| reg.addr() == field.addr && field.mask & !bits == 0),
+                "{} covers undocumented bits",
+                field.name
+            );
+        }
+
+        for (name, reg, bits) in DOCUMENTED {
+            let covering: Vec<_> = fields
+                .iter()
+                .filter(|field| field.addr == reg.addr())
+                .collect();
+            for bit in (0..8).filter(|bit| bits & 1 << bit != 0) {
+                let names: Vec<_> = covering
+                    .iter()
+                    .filter(|field| field.mask & 1 << bit != 0)
+                    .map(|field| field.name)
+                    .collect();
+                assert_eq!(
+                    names.len(),
+                    1,
+                    "{name} bit {bit} of {bits:#010b} is covered by [{}]",
+                    names.join(", ")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn xy_swapped_is_in_feed_config2() {
+        assert_eq!(XYSwapped::REGISTER.addr(), Bank0::FEED_CONFIG2.addr());
+        assert_eq!(XYSwapped::Swapped.bits(), XYSwapped::BITMASK);
+    }
+
+    #[test]
+    fn tap_mode_reads_both_disable_bits() {
+        assert!(matches!(
+            TapMode::from_reg(0b0000_0110),
+            Ok(TapMode::AllAndSecondaryTapDisable)
+        ));
+        assert_eq!(TapMode::AllAndSecondaryTapDisable.bits(), 0b0000_0110);
+    }
+}
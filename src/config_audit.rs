@@ -0,0 +1,139 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    register::{Bank0, Register},
+    ConfigField, ConfigMismatch, ConfigRegister, Error, Event, FeedState, Module, Pinnacle,
+    PositionReportingMode,
+};
+
+/// Periodic read-back of the feed configuration registers, detecting bits flipped without the
+/// driver writing them, e.g. by EMI, see `Tm040040::set_config_audit()`.
+///
+/// FEED_CONFIG1 and FEED_CONFIG2 are compared to the values last written through the driver,
+/// or to the values read by the first audit if the driver didn't write them yet. Differing
+/// fields are raised as `Event::ConfigDrift`. Audits only run while reports are read, or when
+/// `Tm040040::audit_config()` is called.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConfigAudit {
+    /// Number of reports between audits, 0 to not audit by report count
+    pub interval_reports: u16,
+    /// Time between audits in milliseconds, 0 to not audit by time, requires a clock to be set
+    pub interval_ms: u32,
+    /// Write the expected values back after drift was found
+    pub restore: bool,
+}
+
+impl Default for ConfigAudit {
+    fn default() -> Self {
+        Self {
+            interval_reports: 0,
+            interval_ms: 5000,
+            restore: true,
+        }
+    }
+}
+
+/// Values of FEED_CONFIG1 and FEED_CONFIG2 as last written through the driver
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct FeedConfigShadow {
+    values: [Option<u8>; 2],
+}
+
+impl FeedConfigShadow {
+    /// Remember a value written to a register, ignoring other registers than the feed
+    /// configuration
+    pub(crate) fn written(&mut self, addr: u8, value: u8) {
+        if let Some(index) = Self::index(addr) {
+            self.values[index] = Some(value);
+        }
+    }
+
+    fn index(addr: u8) -> Option<usize> {
+        match addr {
+            addr if addr == Bank0::FEED_CONFIG1.addr() => Some(0),
+            addr if addr == Bank0::FEED_CONFIG2.addr() => Some(1),
+            _ => None,
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Read back the feed configuration registers now, returning the fields that drifted.
+    ///
+    /// Raises `Event::ConfigDrift` and, if configured, restores the expected values. Runs
+    /// without an audit being set, then drift is reported but not restored.
+    pub fn audit_config(&mut self) -> Result<ConfigMismatch, Error<E, PinError>> {
+        self.shadows.reports_since_audit = 0;
+        self.shadows.last_audit_us = self.now_us();
+
+        let mut found = [0u8; 2];
+        self.read_regs(&Bank0::FEED_CONFIG1, &mut found)?;
+        let expected = self.shadows.feed_config_shadow.values;
+        let mut mismatch = ConfigMismatch::default();
+        for field in ConfigField::ALL {
+            let ConfigRegister::Bank0(addr) = field.register() else {
+                continue;
+            };
+            let Some(index) = FeedConfigShadow::index(addr) else {
+                continue;
+            };
+            let expected = expected[index].unwrap_or(found[index]);
+            mismatch.check(field, (expected ^ found[index]) & field.mask() == 0);
+        }
+        for (index, value) in found.into_iter().enumerate() {
+            self.shadows.feed_config_shadow.values[index].get_or_insert(value);
+        }
+        if mismatch.is_empty() {
+            return Ok(mismatch);
+        }
+
+        self.emit(Event::ConfigDrift(mismatch));
+        if self.shadows.config_audit.is_some_and(|audit| audit.restore) {
+            let [feed_config1, feed_config2] = self.shadows.feed_config_shadow.values;
+            if let Some(value) = feed_config1 {
+                self.write_reg(&Bank0::FEED_CONFIG1, value)?;
+            }
+            if let Some(value) = feed_config2 {
+                self.write_reg(&Bank0::FEED_CONFIG2, value)?;
+            }
+        }
+
+        Ok(mismatch)
+    }
+
+    /// Audit the feed configuration if due, called for every report read
+    pub(crate) fn audit_config_if_due(&mut self) -> Result<(), Error<E, PinError>> {
+        let Some(audit) = self.shadows.config_audit else {
+            return Ok(());
+        };
+        self.shadows.reports_since_audit = self.shadows.reports_since_audit.saturating_add(1);
+        let due_by_reports = audit.interval_reports != 0
+            && self.shadows.reports_since_audit >= audit.interval_reports;
+        let due_by_time = audit.interval_ms != 0
+            && match (self.shadows.last_audit_us, self.now_us()) {
+                (Some(last_us), Some(now_us)) => {
+                    now_us.saturating_sub(last_us) >= audit.interval_ms as u64 * 1000
+                }
+                // Start the interval at the first report
+                (None, now_us) => {
+                    self.shadows.last_audit_us = now_us;
+                    false
+                }
+                (Some(_), None) => false,
+            };
+        if due_by_reports || due_by_time {
+            self.audit_config()?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use embedded_hal::digital::{self, Error as _, ErrorType, InputPin};
+
+use crate::PinFault;
+
+/// How the hardware data ready (HW_DR) line signals pending reports.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DataReadySignal {
+    /// The line is asserted for as long as a report is pending. Reports are only read while
+    /// the line is high.
+    #[default]
+    Level,
+    /// The line is treated as an edge notification, e.g. when it is open-drain and shared
+    /// between several touchpads. A report found pending right after clearing the flags (see
+    /// `set_dr_recheck()`) is remembered and read on the next call even if the line is low by
+    /// then, as no new edge will be signaled for it.
+    Edge,
+}
+
+/// What to do when reading the hardware data ready line fails, e.g. on a flaky GPIO expander.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum PinErrorPolicy {
+    /// Return the error
+    #[default]
+    Fail,
+    /// Fall back to polling the software data ready flag for as long as the line can't be
+    /// read. Degradation and recovery are reported to the event sink.
+    PollSoftware,
+}
+
+/// Where the driver learns about pending reports, see `Tm040040::with_data_ready_source()`.
+pub enum DataReadySource<'a, E> {
+    /// The hardware data ready line on a directly connected pin
+    Pin(&'a mut dyn InputPin<Error = E>),
+    /// A closure reading the hardware data ready line, e.g. through an I²C GPIO expander whose
+    /// reads are slow and fallible, see `PinErrorPolicy`
+    Expander(&'a mut dyn FnMut() -> Result<bool, E>),
+    /// No hardware data ready line, the software data ready flag is polled instead
+    Software,
+    /// The hardware data ready line is handled elsewhere, e.g. when reads are only triggered by
+    /// its interrupt. Every read polls the software data ready flag, like with `Software`.
+    AlwaysReady,
+}
+
+impl<E: digital::Error> DataReadySource<'_, E> {
+    /// Read the hardware data ready line, `None` if there is none
+    pub(crate) fn is_high(&mut self) -> Option<Result<bool, E>> {
+        match self {
+            Self::Pin(pin) => Some(pin.is_high()),
+            Self::Expander(read) => Some(read()),
+            Self::Software | Self::AlwaysReady => None,
+        }
+    }
+}
+
+/// A data ready pin with its error erased to a `PinFault`, so the driver's errors are
+/// `ErasedError<E>` regardless of the pin type
+///
+/// ```rust,ignore
+/// let mut data_ready = ErasedPin(pin);
+/// let trackpad = Tm040040::new(i2c, Address::Primary, &mut data_ready);
+/// ```
+pub struct ErasedPin<P>(pub P);
+
+impl<P: InputPin> ErrorType for ErasedPin<P> {
+    type Error = PinFault;
+}
+
+impl<P: InputPin> InputPin for ErasedPin<P> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_high().map_err(|error| PinFault(error.kind()))
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.0.is_low().map_err(|error| PinFault(error.kind()))
+    }
+}
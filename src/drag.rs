@@ -0,0 +1,163 @@
+use crate::RelativeData;
+
+/// When a tap drag ends
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum DragRelease {
+    /// The button is released when the finger is lifted
+    #[default]
+    OnLift,
+    /// The button stays held across lifts, so the finger can be repositioned, until the next
+    /// tap releases it
+    OnTap,
+}
+
+/// Timing of the tap drag extender
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragConfig {
+    /// Maximum time between a tap and the start of the drag, in milliseconds
+    pub double_tap_window_ms: u32,
+    /// Time without reports after which the finger counts as lifted, in milliseconds
+    /// Relative reports carry no Z level and the touchpad sends none while the finger rests
+    /// still, so a pause longer than this is taken for a lift. Raise it for users pausing
+    /// mid-drag, at the cost of releasing later after a lift, or use `DragRelease::OnTap`.
+    pub lift_timeout_ms: u32,
+    /// When the drag ends
+    pub release: DragRelease,
+}
+
+impl Default for DragConfig {
+    fn default() -> Self {
+        Self {
+            double_tap_window_ms: 300,
+            lift_timeout_ms: 50,
+            release: DragRelease::OnLift,
+        }
+    }
+}
+
+/// Turns tap-tap-hold in relative mode into a sustained primary button hold, for dragging
+/// over HID.
+///
+/// The hardware only reports taps as momentary button presses. After a tap, a touch starting
+/// to move within the double tap window starts a drag, holding the primary button until the
+/// drag is released. Lifts are detected by the absence of reports, so `update()` is also
+/// called when no report was read. Relative mode can't tell a lift from the finger resting
+/// still, see `DragConfig::lift_timeout_ms`.
+#[derive(Clone, Copy, Debug)]
+pub struct TapDrag {
+    config: DragConfig,
+    last_report_us: Option<u64>,
+    last: Option<RelativeData>,
+    touching: bool,
+    tap_us: Option<u64>,
+    dragging: bool,
+}
+
+impl TapDrag {
+    /// Create a new tap drag extender
+    pub fn new(config: DragConfig) -> Self {
+        Self {
+            config,
+            last_report_us: None,
+            last: None,
+            touching: false,
+            tap_us: None,
+            dragging: false,
+        }
+    }
+
+    /// Get the configured timing
+    pub fn config(&self) -> &DragConfig {
+        &self.config
+    }
+
+    /// Whether a drag is in progress, holding the primary button
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// Process the result of a relative read at `timestamp_us`, returning the report with the
+    /// primary button adjusted.
+    ///
+    /// Without a report, this returns a report releasing the button (without motion) when a
+    /// drag ends because the finger was lifted.
+    pub fn update(
+        &mut self,
+        timestamp_us: u64,
+        data: Option<RelativeData>,
+    ) -> Option<RelativeData> {
+        let lifted = self.last_report_us.is_none_or(|last| {
+            timestamp_us.saturating_sub(last) > self.config.lift_timeout_ms as u64 * 1000
+        });
+
+        let Some(mut data) = data else {
+            if !lifted || !self.touching {
+                return None;
+            }
+            self.touching = false;
+            if !self.dragging || self.config.release != DragRelease::OnLift {
+                return None;
+            }
+            self.dragging = false;
+            return self.last.map(|last| RelativeData {
+                primary_pressed: false,
+                x_delta: 0,
+                y_delta: 0,
+                wheel: 0,
+                ..last
+            });
+        };
+
+        if self.dragging && lifted && self.config.release == DragRelease::OnLift {
+            // Lifted without `update()` being called in between, this report releases
+            self.dragging = false;
+            self.touching = false;
+        }
+
+        let was_pressed = self.last.is_some_and(|last| last.primary_pressed);
+        let tap = data.primary_pressed && !was_pressed;
+        let moving = data.x_delta != 0 || data.y_delta != 0;
+        let new_contact = moving && (lifted || !self.touching);
+        self.last_report_us = Some(timestamp_us);
+        self.last = Some(data);
+
+        if self.dragging {
+            if tap && self.config.release == DragRelease::OnTap {
+                // The releasing tap is consumed
+                self.dragging = false;
+                data.primary_pressed = false;
+            } else {
+                data.primary_pressed = true;
+                self.touching |= moving;
+            }
+            return Some(data);
+        }
+
+        if tap {
+            // Taps are reported after the finger was lifted. A second tap in the window is a
+            // double click and doesn't start a drag.
+            let double = self
+                .tap_us
+                .is_some_and(|tap_us| self.in_window(tap_us, timestamp_us));
+            self.tap_us = (!double).then_some(timestamp_us);
+            self.touching = false;
+            return Some(data);
+        }
+
+        if new_contact {
+            self.touching = true;
+            if let Some(tap_us) = self.tap_us.take() {
+                if self.in_window(tap_us, timestamp_us) {
+                    self.dragging = true;
+                    data.primary_pressed = true;
+                }
+            }
+        }
+
+        Some(data)
+    }
+
+    fn in_window(&self, tap_us: u64, timestamp_us: u64) -> bool {
+        timestamp_us.saturating_sub(tap_us) <= self.config.double_tap_window_ms as u64 * 1000
+    }
+}
@@ -0,0 +1,129 @@
+use core::fmt::Debug;
+
+use embedded_hal::{
+    digital::{self, ErrorKind as PinErrorKind, ErrorType as PinErrorType, InputPin},
+    i2c::{self, ErrorKind, ErrorType, I2c, Operation, SevenBitAddress},
+};
+use embedded_hal_02::{
+    blocking::i2c::{Write, WriteRead},
+    digital::v2::InputPin as InputPin02,
+};
+
+use crate::{I2cAddress, Module, NoFeed, Pinnacle, Relative};
+
+/// Error of a bus or pin implementing the embedded-hal 0.2 traits
+#[derive(Debug)]
+pub struct Eh0Error<E>(pub E);
+
+impl<E: Debug> i2c::Error for Eh0Error<E> {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl<E: Debug> digital::Error for Eh0Error<E> {
+    fn kind(&self) -> PinErrorKind {
+        PinErrorKind::Other
+    }
+}
+
+/// An I²C bus implementing the embedded-hal 0.2 `Write` and `WriteRead` traits, e.g. a
+/// `shared-bus` proxy
+///
+/// Transactions are split into writes and write-reads, which is all the driver uses. Reads
+/// without a preceding write are issued as write-reads with nothing to write.
+pub struct Eh0I2c<I>(pub I);
+
+impl<I, E> ErrorType for Eh0I2c<I>
+where
+    I: Write<Error = E> + WriteRead<Error = E>,
+    E: Debug,
+{
+    type Error = Eh0Error<E>;
+}
+
+impl<I, E> I2c for Eh0I2c<I>
+where
+    I: Write<Error = E> + WriteRead<Error = E>,
+    E: Debug,
+{
+    fn transaction(
+        &mut self,
+        address: SevenBitAddress,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let mut operations = operations.iter_mut().peekable();
+        while let Some(operation) = operations.next() {
+            match operation {
+                Operation::Write(bytes) => {
+                    match operations.next_if(|next| matches!(next, Operation::Read(_))) {
+                        Some(Operation::Read(buffer)) => self.write_read(address, bytes, buffer)?,
+                        _ => self.write(address, bytes)?,
+                    }
+                }
+                Operation::Read(buffer) => self.write_read(address, &[], buffer)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write(&mut self, address: SevenBitAddress, bytes: &[u8]) -> Result<(), Self::Error> {
+        Write::write(&mut self.0, address, bytes).map_err(Eh0Error)
+    }
+
+    fn write_read(
+        &mut self,
+        address: SevenBitAddress,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        WriteRead::write_read(&mut self.0, address, bytes, buffer).map_err(Eh0Error)
+    }
+}
+
+/// A data ready pin implementing the embedded-hal 0.2 `InputPin` trait
+pub struct Eh0Pin<P>(pub P);
+
+impl<P: InputPin02> PinErrorType for Eh0Pin<P>
+where
+    P::Error: Debug,
+{
+    type Error = Eh0Error<P::Error>;
+}
+
+impl<P: InputPin02> InputPin for Eh0Pin<P>
+where
+    P::Error: Debug,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        InputPin02::is_high(&self.0).map_err(Eh0Error)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        InputPin02::is_low(&self.0).map_err(Eh0Error)
+    }
+}
+
+impl<'a, I, E, M: Module, PE> Pinnacle<'a, Eh0I2c<I>, M, Relative, NoFeed, Eh0Error<PE>>
+where
+    I: Write<Error = E> + WriteRead<Error = E>,
+    E: Debug,
+    PE: Debug,
+{
+    /// Create a new trackpad instance on a bus and data ready pin implementing the
+    /// embedded-hal 0.2 traits
+    /// The pin is wrapped by the caller, as the driver only borrows it:
+    ///
+    /// ```rust,ignore
+    /// let mut data_ready = Eh0Pin(pin);
+    /// let trackpad = Tm040040::new_eh0(i2c, Address::Primary, &mut data_ready);
+    /// ```
+    pub fn new_eh0(
+        i2c: I,
+        address: impl Into<I2cAddress>,
+        hardware_data_ready: &'a mut Eh0Pin<impl InputPin02<Error = PE>>,
+    ) -> Self {
+        Self::new(Eh0I2c(i2c), address, hardware_data_ready)
+    }
+}
@@ -0,0 +1,225 @@
+//! Extended register access (ERA).
+//!
+//! Besides the registers of bank 0, the Pinnacle exposes a 16-bit address space of extended
+//! registers, which are accessed indirectly through the ERA registers of bank 0. The extended
+//! registers are grouped in banks of 256 registers, selected by the high address byte.
+#![allow(non_camel_case_types)]
+
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    error::SensorError, register::Bank0, Error, FeedMode, FeedState, Module, Pinnacle,
+    PositionReportingMode,
+};
+
+/// Starts reading the extended register at the ERA address
+const ERA_CONTROL_READ: u8 = 0b001;
+/// Starts writing the ERA value to the extended register at the ERA address
+const ERA_CONTROL_WRITE: u8 = 0b010;
+/// Increments the ERA address after each access
+const ERA_CONTROL_AUTO_INCREMENT: u8 = 0b100;
+/// Maximum number of polls of the ERA control register before giving up
+const ERA_MAX_POLLS: u32 = 1000;
+
+/// Documented extended registers
+///
+/// Other registers, e.g. ones found to tune tap detection on a specific firmware, can be
+/// accessed by address with `read_era()`/`write_era()`, at the risk of them differing between
+/// firmware versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum Registers {
+    /// Minimum Z level for wide touches on the X axis, tunes edge sensitivity
+    X_AXIS_WIDE_Z_MIN = 0x0149,
+    /// Minimum Z level for wide touches on the Y axis, tunes edge sensitivity
+    Y_AXIS_WIDE_Z_MIN = 0x0168,
+    /// Track ADC configuration, bits 6 and 7 select the ADC attenuation
+    TRACK_ADC_CONFIG = 0x0187,
+}
+
+impl Registers {
+    /// Address of the register
+    pub fn addr(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Bank of the register
+    pub fn bank(&self) -> Bank {
+        match self {
+            Self::X_AXIS_WIDE_Z_MIN | Self::Y_AXIS_WIDE_Z_MIN | Self::TRACK_ADC_CONFIG => {
+                Bank::Bank1
+            }
+        }
+    }
+}
+
+/// A bank of 256 extended registers, selected by the high byte of the ERA address
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bank {
+    Bank0 = 0,
+    Bank1 = 1,
+    Bank2 = 2,
+    Bank3 = 3,
+}
+
+impl Bank {
+    /// Extended register address of the register at `offset` within the bank
+    pub fn addr(self, offset: u8) -> u16 {
+        u16::from_be_bytes([self as u8, offset])
+    }
+}
+
+impl TryFrom<u8> for Bank {
+    type Error = SensorError;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Bank0),
+            1 => Ok(Self::Bank1),
+            2 => Ok(Self::Bank2),
+            3 => Ok(Self::Bank3),
+            _ => Err(SensorError::BankOutOfRange),
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Read a documented extended register
+    pub fn read_era_register(&mut self, reg: Registers) -> Result<u8, Error<E, PinError>> {
+        self.read_era(reg.addr())
+    }
+
+    /// Write a documented extended register
+    pub fn write_era_register(
+        &mut self,
+        reg: Registers,
+        value: u8,
+    ) -> Result<(), Error<E, PinError>> {
+        self.write_era(reg.addr(), value)
+    }
+
+    /// Read the register at `offset` within `bank`
+    pub fn read_bank(&mut self, bank: Bank, offset: u8) -> Result<u8, Error<E, PinError>> {
+        self.read_era(bank.addr(offset))
+    }
+
+    /// Write the register at `offset` within `bank`
+    pub fn write_bank(
+        &mut self,
+        bank: Bank,
+        offset: u8,
+        value: u8,
+    ) -> Result<(), Error<E, PinError>> {
+        self.write_era(bank.addr(offset), value)
+    }
+
+    /// Run several extended register accesses, pausing feed only once.
+    ///
+    /// Within the batch a bank is only selected when it differs from the one of the previous
+    /// access. Batches can be nested, feed is restored when the outermost batch ends.
+    pub fn era_batch<T>(
+        &mut self,
+        access: impl FnOnce(&mut Self) -> Result<T, Error<E, PinError>>,
+    ) -> Result<T, Error<E, PinError>> {
+        if self.bus.era_batch {
+            return access(self);
+        }
+
+        let feed: FeedMode = self.read_field()?;
+        self.set_feed_mode(FeedMode::NoFeed)?;
+        self.bus.era_batch = true;
+        self.bus.era_bank = None;
+
+        let result = access(self);
+
+        self.bus.era_batch = false;
+        self.bus.era_bank = None;
+        self.set_feed_mode(feed)?;
+        self.clear_flags()?;
+
+        result
+    }
+
+    /// Read the extended register at `addr`
+    pub fn read_era(&mut self, addr: u16) -> Result<u8, Error<E, PinError>> {
+        let mut buffer = [0u8];
+        self.read_era_burst(addr, &mut buffer)?;
+
+        Ok(buffer[0])
+    }
+
+    /// Read consecutive extended registers, starting at `addr`
+    /// Feed is paused during the access and restored afterwards.
+    pub fn read_era_burst(
+        &mut self,
+        addr: u16,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<E, PinError>> {
+        let [_, low] = addr.to_be_bytes();
+        let crosses_bank = low as usize + buffer.len() > 0xFF;
+        self.with_era(addr, |pad| {
+            if crosses_bank {
+                // Auto-increment moves on to the next bank
+                pad.bus.era_bank = None;
+            }
+            for value in buffer.iter_mut() {
+                pad.write_reg(
+                    &Bank0::ERA_CONTROL,
+                    ERA_CONTROL_READ | ERA_CONTROL_AUTO_INCREMENT,
+                )?;
+                pad.wait_era()?;
+                *value = pad.read_reg(&Bank0::ERA_VALUE)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Write the extended register at `addr`
+    /// Feed is paused during the access and restored afterwards.
+    pub fn write_era(&mut self, addr: u16, value: u8) -> Result<(), Error<E, PinError>> {
+        self.with_era(addr, |pad| {
+            pad.write_reg(&Bank0::ERA_VALUE, value)?;
+            pad.write_reg(&Bank0::ERA_CONTROL, ERA_CONTROL_WRITE)?;
+            pad.wait_era()
+        })
+    }
+
+    /// Pause feed and set the ERA address for `access`
+    fn with_era(
+        &mut self,
+        addr: u16,
+        access: impl FnOnce(&mut Self) -> Result<(), Error<E, PinError>>,
+    ) -> Result<(), Error<E, PinError>> {
+        self.era_batch(|pad| {
+            let [high, low] = addr.to_be_bytes();
+            if pad.bus.era_bank != Some(high) {
+                pad.bus.era_bank = None;
+                pad.write_reg(&Bank0::ERA_HIGH_BYTE, high)?;
+                pad.bus.era_bank = Some(high);
+            }
+            pad.write_reg(&Bank0::ERA_LOW_BYTE, low)?;
+
+            access(pad)
+        })
+    }
+
+    /// Wait for the current extended register access to finish
+    fn wait_era(&mut self) -> Result<(), Error<E, PinError>> {
+        for _ in 0..ERA_MAX_POLLS {
+            if self.read_reg(&Bank0::ERA_CONTROL)? == 0 {
+                return Ok(());
+            }
+        }
+
+        Err(Error::SensorError(SensorError::EraTimeout))
+    }
+}
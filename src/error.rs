@@ -2,13 +2,49 @@ use core::fmt::Debug;
 
 use embedded_hal::digital;
 
+use crate::{ConfigMismatch, I2cAddress, PositionMode};
+
 #[derive(Debug)]
 pub enum Error<E, PE> {
     /// Some error originating from the communication bus
-    BusError(E),
+    BusError {
+        /// The address of the failed transaction
+        address: I2cAddress,
+        /// The error reported by the bus
+        error: E,
+    },
     /// Some error resulting from interacting with the device
     SensorError(SensorError),
     PinError(PE),
+    /// A packet failed the configured plausibility checks
+    InvalidPacket,
+    /// The hardware data ready line stayed asserted after clearing the status flags, without a
+    /// report pending, see `set_dr_handshake()`
+    HandshakeFailed,
+    /// The configuration read back differs from the expected one, see `verify_config()`
+    ConfigMismatch(ConfigMismatch),
+    /// The touchpad reports in a different position mode than the driver expects, e.g. after
+    /// an external reset, see `set_mode_check()`
+    ModeMismatch {
+        /// The position mode of the driver typestate
+        expected: PositionMode,
+        /// The position mode read back from the touchpad
+        found: PositionMode,
+    },
+}
+
+/// `Error` with the pin error erased to its kind, keeping the pin type out of signatures, see
+/// `ErasedPin`
+pub type ErasedError<E> = Error<E, PinFault>;
+
+/// A pin error erased to its kind, see `ErasedPin`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PinFault(pub digital::ErrorKind);
+
+impl digital::Error for PinFault {
+    fn kind(&self) -> digital::ErrorKind {
+        self.0
+    }
 }
 
 #[derive(Debug)]
@@ -25,6 +61,21 @@ pub enum SensorError {
     BankOutOfRange,
     /// Attempted to write to a read-only register
     WriteToReadOnly,
+    /// The touchpad did not finish calibrating in time
+    CalibrationTimeout,
+    /// The touchpad did not finish an AnyMeas measurement in time
+    MeasurementTimeout,
+    /// The touchpad did not finish an extended register access in time
+    EraTimeout,
+    /// A configuration write was not read back correctly, even after retrying
+    WriteVerifyFailed,
+    /// A post-write time is configured, but no delay is set to wait it, see
+    /// `Tm040040::set_delay()`. The register write was not issued.
+    DelayRequired,
+    /// Imported calibration data was exported from a different firmware version
+    FirmwareMismatch,
+    /// Attempted to create an I²C address from a value outside the valid 7-bit range
+    InvalidAddress(u8),
     /// Attempted to create an AccelRange or GyroRange enum from an invalid
     /// discriminant
     InvalidDiscriminant,
@@ -0,0 +1,194 @@
+use heapless::Vec;
+
+use crate::{
+    AbsoluteData, ConfigMismatch, Edge, FeedMode, MountingOrientation, PositionMode, PowerMode,
+    RelativeData, RelativeEvent,
+};
+
+/// Decoded reports and state changes of the driver, mirrored to an `EventSink`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Event {
+    /// A relative report was returned
+    Relative(RelativeEvent),
+    /// An absolute report was returned
+    Absolute(AbsoluteData),
+    /// The power mode was set
+    PowerMode(PowerMode),
+    /// The feed mode was set
+    FeedMode(FeedMode),
+    /// The position mode was set
+    PositionMode(PositionMode),
+    /// The mounting orientation was set
+    Orientation(MountingOrientation),
+    /// A calibration completed
+    Calibrated,
+    /// The touchpad was suspended
+    Suspended,
+    /// The touchpad was resumed
+    Resumed,
+    /// Reading the hardware data ready line failed, the software data ready flag is polled
+    /// instead, see `PinErrorPolicy::PollSoftware`
+    DataReadyDegraded,
+    /// The hardware data ready line can be read again
+    DataReadyRestored,
+    /// A touch reached an edge of the usable area in absolute mode, reported once per edge
+    /// until the touch leaves it
+    EdgeReached(Edge),
+    /// Feed configuration fields read back differently than last written, see
+    /// `Tm040040::set_config_audit()`
+    ConfigDrift(ConfigMismatch),
+}
+
+impl Event {
+    /// Priority of the event, see `EventQueue`
+    pub fn priority(&self) -> EventPriority {
+        match self {
+            Self::Relative(RelativeEvent::ButtonPress(_)) => EventPriority::Button,
+            Self::Relative(RelativeEvent::Motion(_)) | Self::Absolute(_) | Self::EdgeReached(_) => {
+                EventPriority::Motion
+            }
+            _ => EventPriority::Status,
+        }
+    }
+}
+
+/// Priority of an event, higher priorities are delivered first by `EventQueue`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EventPriority {
+    /// State changes of the driver
+    Status,
+    /// Motion and position reports
+    Motion,
+    /// Button changes, e.g. taps
+    Button,
+}
+
+/// Receives every decoded report and state change of the driver, e.g. for black-box recorders
+/// or telemetry, see `Tm040040::set_event_sink()`.
+pub trait EventSink {
+    /// Called for every event, after it took effect
+    fn event(&mut self, event: &Event);
+}
+
+impl<F: FnMut(&Event)> EventSink for F {
+    fn event(&mut self, event: &Event) {
+        self(event)
+    }
+}
+
+/// A queue of up to `N` events, set as event sink and drained by a slower consumer, e.g. a
+/// host connection.
+///
+/// Events are drained by priority, oldest first within a priority, so a tap is never delayed
+/// behind a backlog of motion. When full, the two oldest relative motion events with the same
+/// buttons are coalesced by summing their deltas, or else the oldest event of lower priority
+/// than the new one is dropped. If neither makes room, the new event is dropped.
+#[derive(Clone, Debug, Default)]
+pub struct EventQueue<const N: usize> {
+    events: Vec<Event, N>,
+    dropped: u32,
+}
+
+impl<const N: usize> EventQueue<N> {
+    /// Create an empty queue
+    pub const fn new() -> Self {
+        Self {
+            events: Vec::new(),
+            dropped: 0,
+        }
+    }
+
+    /// Queue an event, making room if full
+    pub fn push(&mut self, event: Event) {
+        if self.events.is_full() && !self.coalesce() && !self.evict(event.priority()) {
+            self.dropped = self.dropped.wrapping_add(1);
+            return;
+        }
+        // Can't fail, there is room now
+        let _ = self.events.push(event);
+    }
+
+    /// Take the oldest event of the highest priority
+    pub fn pop(&mut self) -> Option<Event> {
+        let priority = self.events.iter().map(Event::priority).max()?;
+        let index = self
+            .events
+            .iter()
+            .position(|event| event.priority() == priority)?;
+
+        Some(self.events.remove(index))
+    }
+
+    /// Take all events, highest priority first
+    pub fn drain(&mut self) -> impl Iterator<Item = Event> + '_ {
+        core::iter::from_fn(|| self.pop())
+    }
+
+    /// Number of queued events
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Whether no events are queued
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Number of events dropped because the queue was full
+    pub fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Merge the oldest pair of consecutive relative motion events with the same buttons,
+    /// returning whether room was made
+    fn coalesce(&mut self) -> bool {
+        let motion = |event: &Event| match event {
+            Event::Relative(RelativeEvent::Motion(data)) => Some(*data),
+            _ => None,
+        };
+        let mut previous: Option<(usize, RelativeData)> = None;
+        for index in 0..self.events.len() {
+            let Some(newer) = motion(&self.events[index]) else {
+                continue;
+            };
+            if let Some((first, older)) = previous {
+                if older.buttons() == newer.buttons() {
+                    let merged = RelativeData {
+                        x_delta: older.x_delta.saturating_add(newer.x_delta),
+                        y_delta: older.y_delta.saturating_add(newer.y_delta),
+                        wheel: older.wheel.saturating_add(newer.wheel),
+                        ..newer
+                    };
+                    self.events[first] = Event::Relative(RelativeEvent::Motion(merged));
+                    self.events.remove(index);
+
+                    return true;
+                }
+            }
+            previous = Some((index, newer));
+        }
+
+        false
+    }
+
+    /// Drop the oldest event of lower priority than `priority`, returning whether room was made
+    fn evict(&mut self, priority: EventPriority) -> bool {
+        let Some(index) = self
+            .events
+            .iter()
+            .position(|event| event.priority() < priority)
+        else {
+            return false;
+        };
+        self.events.remove(index);
+        self.dropped = self.dropped.wrapping_add(1);
+
+        true
+    }
+}
+
+impl<const N: usize> EventSink for EventQueue<N> {
+    fn event(&mut self, event: &Event) {
+        self.push(*event);
+    }
+}
@@ -0,0 +1,73 @@
+//! Register file standing in for a touchpad on the bus, for unit tests of the driver
+
+use core::convert::Infallible;
+
+use embedded_hal::{
+    digital,
+    i2c::{self, Operation},
+};
+
+use crate::{config::Mask, PINNACLE_FIRMWARE_ID};
+
+/// Bank 0 registers of a touchpad, read and written through the register access protocol
+pub(crate) struct FakeBus {
+    pub(crate) regs: [u8; 0x20],
+}
+
+impl FakeBus {
+    pub(crate) fn new() -> Self {
+        let mut regs = [0; 0x20];
+        regs[0] = PINNACLE_FIRMWARE_ID;
+
+        Self { regs }
+    }
+}
+
+/// Data ready line at a fixed level
+#[cfg_attr(not(feature = "relative"), allow(dead_code))]
+pub(crate) struct FakePin(pub(crate) bool);
+
+impl digital::ErrorType for FakePin {
+    type Error = Infallible;
+}
+
+impl digital::InputPin for FakePin {
+    fn is_high(&mut self) -> Result<bool, Infallible> {
+        Ok(self.0)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Infallible> {
+        Ok(!self.0)
+    }
+}
+
+impl i2c::ErrorType for FakeBus {
+    type Error = Infallible;
+}
+
+impl i2c::I2c for FakeBus {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        operations: &mut [Operation<'_>],
+    ) -> Result<(), Infallible> {
+        let mut addr = 0;
+        for operation in operations {
+            match operation {
+                Operation::Write(bytes) => {
+                    addr = (bytes[0] & 0x1f) as usize;
+                    if bytes[0] & Mask::Read as u8 == Mask::Write as u8 {
+                        for (offset, &value) in bytes[1..].iter().enumerate() {
+                            self.regs[addr + offset] = value;
+                        }
+                    }
+                }
+                Operation::Read(buffer) => {
+                    buffer.copy_from_slice(&self.regs[addr..addr + buffer.len()]);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
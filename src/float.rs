@@ -0,0 +1,154 @@
+use core::ops::Range;
+
+use crate::{
+    AbsoluteData, RelativeData, SpeedPreset, PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER,
+    PINNACLE_Y_UPPER,
+};
+
+impl AbsoluteData {
+    /// The position within the usable area of the sensor, from 0.0 to 1.0 on both axes
+    /// Positions changed by a `Transform` can lie outside this range.
+    pub fn normalized(&self) -> (f32, f32) {
+        (
+            normalize(self.x_pos, PINNACLE_X_LOWER, PINNACLE_X_UPPER),
+            normalize(self.y_pos, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER),
+        )
+    }
+
+    /// The position mapped linearly from the usable area of the sensor onto the given ranges,
+    /// e.g. screen coordinates
+    pub fn map(&self, x: Range<f32>, y: Range<f32>) -> (f32, f32) {
+        let (x_norm, y_norm) = self.normalized();
+
+        (
+            x.start + x_norm * (x.end - x.start),
+            y.start + y_norm * (y.end - y.start),
+        )
+    }
+}
+
+fn normalize(pos: u16, lower: u16, upper: u16) -> f32 {
+    (pos as f32 - lower as f32) / (upper - lower) as f32
+}
+
+impl RelativeData {
+    /// The deltas with the gain of a speed preset applied, without rounding
+    pub fn scaled(&self, preset: SpeedPreset) -> (f32, f32) {
+        let gain = preset.gain_f32();
+
+        (self.x_delta as f32 * gain, self.y_delta as f32 * gain)
+    }
+}
+
+impl SpeedPreset {
+    /// The gain of this preset
+    pub fn gain_f32(self) -> f32 {
+        let (num, den) = self.gain();
+        num as f32 / den as f32
+    }
+}
+
+/// Pointer acceleration for relative deltas: the gain rises linearly with speed above a
+/// threshold, up to a maximum.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ballistics {
+    /// Gain below the threshold speed
+    pub gain: f32,
+    /// Speed in counts per second above which the gain increases
+    pub threshold: f32,
+    /// Increase of the gain per count per second above the threshold
+    pub acceleration: f32,
+    /// Maximum gain
+    pub max_gain: f32,
+}
+
+impl Default for Ballistics {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            threshold: 200.0,
+            acceleration: 0.002,
+            max_gain: 4.0,
+        }
+    }
+}
+
+impl Ballistics {
+    /// Gain at the given speed in counts per second
+    pub fn gain_at(&self, speed: f32) -> f32 {
+        let excess = (speed - self.threshold).max(0.0);
+        (self.gain * (1.0 + self.acceleration * excess)).min(self.max_gain)
+    }
+
+    /// Accelerate the deltas of a report, `interval_us` being the time since the last report
+    pub fn apply(&self, data: &RelativeData, interval_us: u32) -> (f32, f32) {
+        let (dx, dy) = (data.x_delta as f32, data.y_delta as f32);
+        let gain = if interval_us == 0 {
+            self.gain
+        } else {
+            self.gain_at(magnitude(dx, dy) * 1_000_000.0 / interval_us as f32)
+        };
+
+        (dx * gain, dy * gain)
+    }
+}
+
+/// Estimates the velocity of a finger in absolute mode, in counts per second
+#[derive(Clone, Copy, Debug)]
+pub struct Velocity {
+    /// Weight of the previous estimate when smoothing, from 0.0 (no smoothing) to below 1.0
+    pub smoothing: f32,
+    touch_z: u8,
+    last: Option<(u64, f32, f32)>,
+    velocity: (f32, f32),
+}
+
+impl Velocity {
+    /// New estimator, counting reports with a Z level of at least `touch_z` as touches
+    pub fn new(touch_z: u8, smoothing: f32) -> Self {
+        Self {
+            smoothing,
+            touch_z,
+            last: None,
+            velocity: (0.0, 0.0),
+        }
+    }
+
+    /// Process a report read at `timestamp_us`, returning the current velocity while touching
+    pub fn update(&mut self, timestamp_us: u64, data: &AbsoluteData) -> Option<(f32, f32)> {
+        if data.z_level < self.touch_z {
+            self.last = None;
+            self.velocity = (0.0, 0.0);
+            return None;
+        }
+
+        let (x, y) = (data.x_pos as f32, data.y_pos as f32);
+        if let Some((last_us, last_x, last_y)) = self.last {
+            let interval_us = timestamp_us.saturating_sub(last_us);
+            if interval_us > 0 {
+                let scale = 1_000_000.0 / interval_us as f32;
+                let current = ((x - last_x) * scale, (y - last_y) * scale);
+                self.velocity = (
+                    self.smoothing * self.velocity.0 + (1.0 - self.smoothing) * current.0,
+                    self.smoothing * self.velocity.1 + (1.0 - self.smoothing) * current.1,
+                );
+            }
+        }
+        self.last = Some((timestamp_us, x, y));
+
+        Some(self.velocity)
+    }
+
+    /// Current speed in counts per second
+    pub fn speed(&self) -> f32 {
+        magnitude(self.velocity.0, self.velocity.1)
+    }
+}
+
+/// Approximate length of a vector, without needing `sqrt` (error within about 4%)
+fn magnitude(x: f32, y: f32) -> f32 {
+    let (x, y) = (x.abs(), y.abs());
+    let (max, min) = if x > y { (x, y) } else { (y, x) };
+
+    0.96 * max + 0.4 * min
+}
@@ -0,0 +1,90 @@
+use crate::AbsoluteData;
+
+/// Thresholds of the force click classifier
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForceConfig {
+    /// Minimum Z level of a light press (click)
+    pub light_z: u8,
+    /// Minimum Z level of a firm press (deep click)
+    pub firm_z: u8,
+    /// How far the Z level has to drop below a threshold to leave a press stage
+    pub hysteresis: u8,
+}
+
+impl Default for ForceConfig {
+    fn default() -> Self {
+        Self {
+            light_z: 30,
+            firm_z: 48,
+            hysteresis: 4,
+        }
+    }
+}
+
+/// Press stage changes detected by a `ForceClick`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ForceEvent {
+    /// A light press started, e.g. a primary click
+    Click,
+    /// A firm press started, e.g. a deep or secondary click. Also sent without a preceding
+    /// `Click` if the Z level jumps past both thresholds at once.
+    DeepClick,
+    /// A firm press eased into a light press
+    DeepRelease,
+    /// The press ended, from either stage
+    Release,
+}
+
+/// Stage of the current press
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Stage {
+    None,
+    Light,
+    Firm,
+}
+
+/// Classifies presses by Z level into light and firm presses, emulating force touch
+/// interactions on devices without physical buttons.
+///
+/// The Z level depends on the finger size and overlay, so the thresholds usually need tuning
+/// for a device.
+#[derive(Clone, Copy, Debug)]
+pub struct ForceClick {
+    config: ForceConfig,
+    stage: Stage,
+}
+
+impl ForceClick {
+    /// Create a new force click classifier
+    pub fn new(config: ForceConfig) -> Self {
+        Self {
+            config,
+            stage: Stage::None,
+        }
+    }
+
+    /// Get the configured thresholds
+    pub fn config(&self) -> &ForceConfig {
+        &self.config
+    }
+
+    /// Process an absolute report, returning the press stage change it caused
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<ForceEvent> {
+        let z = data.z_level;
+        let light_release = self.config.light_z.saturating_sub(self.config.hysteresis);
+        let firm_release = self.config.firm_z.saturating_sub(self.config.hysteresis);
+
+        let (stage, event) = match self.stage {
+            Stage::None if z >= self.config.firm_z => (Stage::Firm, ForceEvent::DeepClick),
+            Stage::None if z >= self.config.light_z => (Stage::Light, ForceEvent::Click),
+            Stage::Light if z >= self.config.firm_z => (Stage::Firm, ForceEvent::DeepClick),
+            Stage::Light if z < light_release => (Stage::None, ForceEvent::Release),
+            Stage::Firm if z < light_release => (Stage::None, ForceEvent::Release),
+            Stage::Firm if z < firm_release => (Stage::Light, ForceEvent::DeepRelease),
+            _ => return None,
+        };
+        self.stage = stage;
+
+        Some(event)
+    }
+}
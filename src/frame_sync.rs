@@ -0,0 +1,15 @@
+/// Called right after each report packet was read from the touchpad, e.g. to sample an IMU or
+/// encoder at the same instant for sensor fusion, see `Tm040040::set_frame_sync()`.
+///
+/// It's called for every packet read, before the driver validates, filters or drops it, so
+/// it should return quickly.
+pub trait FrameSync {
+    /// A packet was read at `timestamp_us`, `None` if no clock is set
+    fn frame_read(&mut self, timestamp_us: Option<u64>);
+}
+
+impl<F: FnMut(Option<u64>)> FrameSync for F {
+    fn frame_read(&mut self, timestamp_us: Option<u64>) {
+        self(timestamp_us)
+    }
+}
@@ -0,0 +1,87 @@
+use core::fmt::Debug;
+
+use embedded_hal::{delay::DelayNs, digital, i2c::I2c};
+
+use crate::{
+    AnyMeasConfig, FeedState, Module, Pinnacle, PositionReportingMode, PINNACLE_FIRMWARE_ID,
+};
+
+/// AnyMeas results with a larger magnitude indicate a saturated ADC
+const ANYMEAS_SATURATION: u16 = 32000;
+
+/// Outcome of a single test check
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CheckResult {
+    /// The check was not run, e.g. because an earlier check failed
+    #[default]
+    Skipped,
+    /// The check passed
+    Passed,
+    /// The check failed
+    Failed,
+}
+
+impl From<bool> for CheckResult {
+    fn from(passed: bool) -> Self {
+        if passed {
+            Self::Passed
+        } else {
+            Self::Failed
+        }
+    }
+}
+
+/// Results of `Tm040040::functional_test()`
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct TestReport {
+    /// The firmware ID read from the touchpad, if it could be read at all
+    pub firmware_id: Option<u8>,
+    /// Whether the firmware ID was read and matches a Pinnacle ASIC
+    pub device_id: CheckResult,
+    /// Whether a forced calibration completed
+    pub calibration: CheckResult,
+    /// The result of the forced AnyMeas measurement
+    pub anymeas_value: Option<i16>,
+    /// Whether the AnyMeas measurement returned a plausible (non-saturated) value
+    pub anymeas: CheckResult,
+}
+
+impl TestReport {
+    /// Whether no check failed
+    pub fn passed(&self) -> bool {
+        ![self.device_id, self.calibration, self.anymeas].contains(&CheckResult::Failed)
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Run a quick functional test of the touchpad, e.g. in a factory fixture
+    /// Checks I²C communication and the firmware ID, calibration and a forced AnyMeas
+    /// measurement. Later checks are skipped if communication fails. The touchpad must not be
+    /// touched.
+    pub fn functional_test(&mut self, delay: &mut impl DelayNs) -> TestReport {
+        let mut report = TestReport {
+            firmware_id: self.device_id().ok(),
+            ..Default::default()
+        };
+        report.device_id = (report.firmware_id == Some(PINNACLE_FIRMWARE_ID)).into();
+        if report.firmware_id.is_none() {
+            return report;
+        }
+
+        report.calibration = self.calibrate(delay).is_ok().into();
+        report.anymeas_value = self.anymeas_measure(&AnyMeasConfig::default(), delay).ok();
+        report.anymeas = report
+            .anymeas_value
+            .is_some_and(|value| value.unsigned_abs() < ANYMEAS_SATURATION)
+            .into();
+
+        report
+    }
+}
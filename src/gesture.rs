@@ -0,0 +1,250 @@
+use crate::{AbsoluteData, HapticHooks, Region, Variant};
+
+/// Thresholds of the software gesture engine
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureConfig {
+    /// Minimum Z level counted as a touch
+    pub touch_z: u8,
+    /// Maximum duration of a tap, in milliseconds
+    pub tap_max_duration_ms: u32,
+    /// Maximum movement during a tap, on the X and Y axis
+    pub tap_max_movement: (u16, u16),
+    /// Minimum movement of a swipe along the X and Y axis, the swipe direction is the axis
+    /// covering the larger part of its threshold
+    pub swipe_min_distance: (u16, u16),
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            touch_z: 4,
+            tap_max_duration_ms: 200,
+            tap_max_movement: (40, 40),
+            swipe_min_distance: (400, 400),
+        }
+    }
+}
+
+impl GestureConfig {
+    /// Default thresholds with the tap movement and swipe distance given in percent of the
+    /// usable area of `variant`, instead of counts
+    /// The axes have different resolutions, so each threshold is scaled by the span of its own
+    /// axis.
+    pub fn from_area_percent(
+        variant: Variant,
+        tap_max_movement: u8,
+        swipe_min_distance: u8,
+    ) -> Self {
+        let (width_mm, height_mm) = variant.dimensions_mm();
+        let width = variant.counts_per_mm() as u32 * width_mm as u32;
+        let height = variant.y_counts_per_mm() as u32 * height_mm as u32;
+        let counts = |percent: u8| {
+            let percent = percent.min(100) as u32;
+            (
+                (width * percent / 100) as u16,
+                (height * percent / 100) as u16,
+            )
+        };
+
+        Self {
+            tap_max_movement: counts(tap_max_movement),
+            swipe_min_distance: counts(swipe_min_distance),
+            ..Self::default()
+        }
+    }
+}
+
+/// Direction of a swipe, in absolute coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    /// Towards lower X positions
+    Left,
+    /// Towards higher X positions
+    Right,
+    /// Towards lower Y positions
+    Up,
+    /// Towards higher Y positions
+    Down,
+}
+
+/// Where a tap happened, e.g. to hit-test UI elements
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TapLocation {
+    /// X position of the centroid of the touch, weighted by Z level
+    pub x: u16,
+    /// Y position of the centroid of the touch, weighted by Z level
+    pub y: u16,
+    /// Highest Z level during the touch
+    pub peak_z: u8,
+}
+
+impl TapLocation {
+    /// Create a tap location
+    pub const fn new(x: u16, y: u16, peak_z: u8) -> Self {
+        Self { x, y, peak_z }
+    }
+}
+
+/// Gestures detected by the software gesture engine
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Gesture {
+    /// A short touch without significant movement
+    Tap(TapLocation),
+    /// A touch moving far in one direction
+    Swipe(SwipeDirection),
+}
+
+/// State of the current contact
+#[derive(Clone, Copy, Debug)]
+struct Contact {
+    start_us: u64,
+    start: (u16, u16),
+    last: (u16, u16),
+    max_movement: (u16, u16),
+    /// Sums of the positions weighted by Z level, and of the Z levels
+    weighted: (u64, u64, u64),
+    peak_z: u8,
+}
+
+/// Software gesture engine, detecting gestures from absolute reports.
+///
+/// Gestures are reported when the finger is lifted, so the touchpad should send Z-idle packets
+/// (the default).
+#[derive(Clone, Copy, Debug)]
+pub struct GestureEngine<'a> {
+    config: GestureConfig,
+    contact: Option<Contact>,
+    tap_exclusions: &'a [Region],
+}
+
+impl<'a> GestureEngine<'a> {
+    /// Create a new gesture engine
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            contact: None,
+            tap_exclusions: &[],
+        }
+    }
+
+    /// Get the configured thresholds
+    pub fn config(&self) -> &GestureConfig {
+        &self.config
+    }
+
+    /// Set regions where taps are ignored, e.g. under a display bezel where accidental taps
+    /// are common
+    /// Swipes starting or ending in the regions are still detected.
+    pub fn set_tap_exclusions(&mut self, regions: &'a [Region]) {
+        self.tap_exclusions = regions;
+    }
+
+    /// Process the next absolute report, read at `timestamp_us`
+    pub fn update(&mut self, timestamp_us: u64, data: &AbsoluteData) -> Option<Gesture> {
+        let position = (data.x_pos, data.y_pos);
+
+        if data.z_level >= self.config.touch_z {
+            let contact = self.contact.get_or_insert(Contact {
+                start_us: timestamp_us,
+                start: position,
+                last: position,
+                max_movement: (0, 0),
+                weighted: (0, 0, 0),
+                peak_z: 0,
+            });
+            contact.max_movement = (
+                contact
+                    .max_movement
+                    .0
+                    .max(position.0.abs_diff(contact.start.0)),
+                contact
+                    .max_movement
+                    .1
+                    .max(position.1.abs_diff(contact.start.1)),
+            );
+            contact.last = position;
+            let z = data.z_level as u64;
+            contact.weighted.0 += position.0 as u64 * z;
+            contact.weighted.1 += position.1 as u64 * z;
+            contact.weighted.2 += z;
+            contact.peak_z = contact.peak_z.max(data.z_level);
+
+            return None;
+        }
+
+        let contact = self.contact.take()?;
+        self.classify(timestamp_us, &contact)
+    }
+
+    /// Process the next absolute report like `update()`, invoking the tap hook of `haptics`
+    /// for detected taps
+    pub fn update_with_haptics(
+        &mut self,
+        timestamp_us: u64,
+        data: &AbsoluteData,
+        haptics: &mut dyn HapticHooks,
+    ) -> Option<Gesture> {
+        let gesture = self.update(timestamp_us, data);
+        if let Some(Gesture::Tap(_)) = gesture {
+            haptics.on_tap();
+        }
+
+        gesture
+    }
+
+    /// Classify a finished contact
+    fn classify(&self, end_us: u64, contact: &Contact) -> Option<Gesture> {
+        let duration_ms = end_us.saturating_sub(contact.start_us) / 1000;
+        if duration_ms <= self.config.tap_max_duration_ms as u64
+            && contact.max_movement.0 <= self.config.tap_max_movement.0
+            && contact.max_movement.1 <= self.config.tap_max_movement.1
+        {
+            let (x_sum, y_sum, z_sum) = contact.weighted;
+            // Only zero with a touch threshold of 0, fall back to the last position then
+            let (x, y) = match (x_sum.checked_div(z_sum), y_sum.checked_div(z_sum)) {
+                (Some(x), Some(y)) => (x as u16, y as u16),
+                _ => contact.last,
+            };
+            if self
+                .tap_exclusions
+                .iter()
+                .any(|region| region.contains(x, y))
+            {
+                return None;
+            }
+            return Some(Gesture::Tap(TapLocation {
+                x,
+                y,
+                peak_z: contact.peak_z,
+            }));
+        }
+
+        let dx = contact.last.0 as i32 - contact.start.0 as i32;
+        let dy = contact.last.1 as i32 - contact.start.1 as i32;
+        let (min_x, min_y) = self.config.swipe_min_distance;
+        let (min_x, min_y) = (min_x as u64, min_y as u64);
+        // Compare the axes relative to their thresholds, dx / min_x against dy / min_y
+        let (abs_dx, abs_dy) = (dx.unsigned_abs() as u64, dy.unsigned_abs() as u64);
+        let direction = if abs_dx * min_y.max(1) >= abs_dy * min_x.max(1) {
+            if abs_dx < min_x {
+                return None;
+            }
+            if dx < 0 {
+                SwipeDirection::Left
+            } else {
+                SwipeDirection::Right
+            }
+        } else {
+            if abs_dy < min_y {
+                return None;
+            }
+            if dy < 0 {
+                SwipeDirection::Up
+            } else {
+                SwipeDirection::Down
+            }
+        };
+
+        Some(Gesture::Swipe(direction))
+    }
+}
@@ -0,0 +1,33 @@
+/// Edges of the usable touchpad area, in output coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Edge {
+    /// Lowest X position
+    Left,
+    /// Highest X position
+    Right,
+    /// Lowest Y position
+    Top,
+    /// Highest Y position
+    Bottom,
+}
+
+/// Hooks invoked by the driver and the gesture engine, e.g. to drive an LRA/ERM haptic driver
+/// in sync with taps and scroll detents.
+///
+/// All hooks do nothing by default. They're called from within the driver's read methods and
+/// should return quickly.
+pub trait HapticHooks {
+    /// A tap was detected, either by the touchpad (a button press in relative mode) or by the
+    /// gesture engine
+    fn on_tap(&mut self) {}
+
+    /// The scroll wheel moved by `detents` whole detents
+    fn on_detent(&mut self, detents: i8) {
+        let _ = detents;
+    }
+
+    /// The finger reached an edge of the usable area in absolute mode
+    fn on_edge_hit(&mut self, edge: Edge) {
+        let _ = edge;
+    }
+}
@@ -0,0 +1,80 @@
+use crate::RelativeData;
+
+/// Integrates relative reports between transmissions over a slow link, e.g. BLE at 20-30 Hz,
+/// so motion isn't lost when the link can't keep up with the report rate.
+///
+/// Every report is passed to `update()`, and `take_accumulated()` returns the summed motion
+/// when the link is ready. Motion exceeding the range of a report is carried over to the next
+/// one. Button changes can't be summed, so `update()` asks for a transmission as soon as the
+/// buttons changed, otherwise only the latest button state is sent.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MotionIntegrator {
+    x: i32,
+    y: i32,
+    wheel: i32,
+    latest: Option<RelativeData>,
+    sent_buttons: u8,
+}
+
+impl MotionIntegrator {
+    /// Create an integrator without accumulated motion
+    pub const fn new() -> Self {
+        Self {
+            x: 0,
+            y: 0,
+            wheel: 0,
+            latest: None,
+            sent_buttons: 0,
+        }
+    }
+
+    /// Add a report, returning whether its buttons changed since the last transmission and it
+    /// should be taken right away
+    pub fn update(&mut self, data: &RelativeData) -> bool {
+        self.x = self.x.saturating_add(data.x_delta as i32);
+        self.y = self.y.saturating_add(data.y_delta as i32);
+        self.wheel = self.wheel.saturating_add(data.wheel as i32);
+        self.latest = Some(*data);
+
+        data.buttons() != self.sent_buttons
+    }
+
+    /// Whether there is motion or a button change to transmit
+    pub fn is_pending(&self) -> bool {
+        self.latest.is_some_and(|latest| {
+            self.x != 0 || self.y != 0 || self.wheel != 0 || latest.buttons() != self.sent_buttons
+        })
+    }
+
+    /// Take the motion summed since the last call, with the buttons and metadata of the latest
+    /// report, `None` if there is nothing to transmit
+    pub fn take_accumulated(&mut self) -> Option<RelativeData> {
+        if !self.is_pending() {
+            return None;
+        }
+        let latest = self.latest?;
+
+        let x_delta = self.x.clamp(i16::MIN as i32, i16::MAX as i32);
+        let y_delta = self.y.clamp(i16::MIN as i32, i16::MAX as i32);
+        let wheel = self.wheel.clamp(i8::MIN as i32, i8::MAX as i32);
+        self.x -= x_delta;
+        self.y -= y_delta;
+        self.wheel -= wheel;
+        self.sent_buttons = latest.buttons();
+
+        Some(RelativeData {
+            x_delta: x_delta as i16,
+            y_delta: y_delta as i16,
+            wheel: wheel as i8,
+            ..latest
+        })
+    }
+
+    /// Drop the accumulated motion, e.g. after the link was lost
+    pub fn reset(&mut self) {
+        *self = Self {
+            sent_buttons: self.sent_buttons,
+            ..Self::new()
+        };
+    }
+}
@@ -0,0 +1,139 @@
+//! Value lists and human readable labels of the config enums, e.g. for settings menus.
+
+use crate::{
+    AdcAttenuation, Address, BusProfile, CoordinateOrigin, FeedMode, FilterMode, GlideExtendMode,
+    IntelliMouseMode, LatencyProfile, MountingOrientation, OverlayMaterial, OverlayThickness,
+    PositionMode, PowerMode, ScrollMode, SpeedPreset, TapMode, XYEnable, XYInverted, XYSwapped,
+};
+
+/// Implement `as_str()`, and `ALL` unless the enum already has it
+macro_rules! labels {
+    (@as_str $ty:ident { $($variant:ident => $label:literal,)* }) => {
+        impl $ty {
+            /// Human readable label of the value
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $label,)*
+                }
+            }
+        }
+    };
+    ($($ty:ident $(($has_all:ident))? { $($variant:ident => $label:literal,)* })*) => {
+        $(
+            labels!(@as_str $ty { $($variant => $label,)* });
+            labels!(@all $ty $(($has_all))? { $($variant,)* });
+        )*
+    };
+    (@all $ty:ident (has_all) { $($variant:ident,)* }) => {};
+    (@all $ty:ident { $($variant:ident,)* }) => {
+        impl $ty {
+            /// All values, in declaration order
+            pub const ALL: [Self; [$(Self::$variant),*].len()] = [$(Self::$variant),*];
+        }
+    };
+}
+
+labels! {
+    Address {
+        Primary => "Primary (0x2A)",
+        Secondary => "Secondary (0x2C)",
+    }
+    PowerMode {
+        Shutdown => "Shutdown",
+        Sleep => "Sleep",
+        Normal => "Normal",
+    }
+    FeedMode {
+        Enabled => "Enabled",
+        NoFeed => "No feed",
+    }
+    PositionMode {
+        Relative => "Relative",
+        Absolute => "Absolute",
+    }
+    FilterMode {
+        Enable => "Enabled",
+        Disable => "Disabled",
+    }
+    XYEnable {
+        Enabled => "X and Y enabled",
+        XDisabled => "X disabled",
+        YDisabled => "Y disabled",
+        XYDisabled => "X and Y disabled",
+    }
+    XYInverted {
+        Normal => "Not inverted",
+        XInverted => "X inverted",
+        YInverted => "Y inverted",
+        XYInverted => "X and Y inverted",
+    }
+    XYSwapped {
+        Normal => "Not swapped",
+        Swapped => "Swapped",
+    }
+    IntelliMouseMode {
+        Disabled => "Disabled",
+        Enabled => "Enabled",
+    }
+    TapMode {
+        Enabled => "All taps enabled",
+        AllTapsDisable => "All taps disabled",
+        SecondaryTapDisable => "Secondary tap disabled",
+        AllAndSecondaryTapDisable => "All and secondary taps disabled",
+    }
+    ScrollMode {
+        Enabled => "Enabled",
+        Disabled => "Disabled",
+    }
+    GlideExtendMode {
+        Enabled => "Enabled",
+        Disabled => "Disabled",
+    }
+    SpeedPreset {
+        Slow => "Slow",
+        Normal => "Normal",
+        Fast => "Fast",
+        Precise => "Precise",
+    }
+    AdcAttenuation {
+        X1 => "1x",
+        X2 => "2x",
+        X3 => "3x",
+        X4 => "4x",
+    }
+    MountingOrientation(has_all) {
+        Normal => "Normal",
+        Rotate90 => "Rotated 90°",
+        Rotate180 => "Rotated 180°",
+        Rotate270 => "Rotated 270°",
+        MirrorX => "Mirrored horizontally",
+        MirrorY => "Mirrored vertically",
+        Transpose => "Transposed",
+        AntiTranspose => "Anti-transposed",
+    }
+    CoordinateOrigin {
+        Native => "Native",
+        TopLeft => "Top left",
+        BottomLeft => "Bottom left",
+        Center => "Center",
+    }
+    OverlayThickness {
+        None => "None",
+        HalfMm => "0.5mm",
+        OneMm => "1mm",
+        TwoMm => "2mm",
+    }
+    OverlayMaterial {
+        Plastic => "Plastic",
+        Glass => "Glass",
+    }
+    LatencyProfile {
+        Smooth => "Smooth",
+        Balanced => "Balanced",
+        Raw => "Raw",
+    }
+    BusProfile {
+        Standard => "Standard",
+        LongCable => "Long cable",
+    }
+}
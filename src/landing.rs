@@ -0,0 +1,123 @@
+use heapless::Deque;
+
+use crate::{AbsoluteData, RelativeData};
+
+/// Frames dropped by `SoftLanding` at the start and end of every contact
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoftLandingConfig {
+    /// Number of frames dropped after touchdown
+    pub skip_first: u8,
+    /// Number of frames dropped before liftoff, at most the capacity of the `SoftLanding`
+    pub skip_last: u8,
+    /// Time without reports after which the finger counts as lifted in relative mode, in
+    /// milliseconds
+    pub lift_timeout_ms: u32,
+}
+
+impl Default for SoftLandingConfig {
+    fn default() -> Self {
+        Self {
+            skip_first: 2,
+            skip_last: 2,
+            lift_timeout_ms: 50,
+        }
+    }
+}
+
+/// Suppresses the cursor jumps caused by a finger rolling onto and off the sensor, by dropping
+/// the first and last frames of every contact.
+///
+/// Dropping the last frames delays every report by `skip_last` frames, up to `N`. In relative
+/// mode lifts are detected by the absence of reports, so `update()` is also called when no
+/// report was read. Reports with a button pressed, e.g. taps, are passed through unchanged.
+#[derive(Clone, Debug)]
+pub struct SoftLanding<T, const N: usize> {
+    config: SoftLandingConfig,
+    frames: u8,
+    last_report_us: Option<u64>,
+    delayed: Deque<T, N>,
+}
+
+impl<T, const N: usize> SoftLanding<T, N> {
+    /// Create a new soft landing filter
+    pub fn new(config: SoftLandingConfig) -> Self {
+        Self {
+            config,
+            frames: 0,
+            last_report_us: None,
+            delayed: Deque::new(),
+        }
+    }
+
+    /// Get the configured frame counts
+    pub fn config(&self) -> &SoftLandingConfig {
+        &self.config
+    }
+
+    /// Start a new contact, dropping the delayed frames of the last one
+    fn lift(&mut self) {
+        self.frames = 0;
+        self.delayed.clear();
+    }
+
+    /// Process a frame of the current contact
+    fn touch(&mut self, frame: T) -> Option<T> {
+        if self.frames < self.config.skip_first {
+            self.frames += 1;
+            return None;
+        }
+        let delay = (self.config.skip_last as usize).min(N);
+        if delay == 0 {
+            return Some(frame);
+        }
+        let released = if self.delayed.len() >= delay {
+            self.delayed.pop_front()
+        } else {
+            None
+        };
+        // Can't fail, a frame was released if the queue was full
+        let _ = self.delayed.push_back(frame);
+
+        released
+    }
+}
+
+impl<const N: usize> SoftLanding<RelativeData, N> {
+    /// Process the result of a relative read at `timestamp_us`, returning the report to use
+    pub fn update(
+        &mut self,
+        timestamp_us: u64,
+        data: Option<RelativeData>,
+    ) -> Option<RelativeData> {
+        let lifted = self.last_report_us.is_none_or(|last| {
+            timestamp_us.saturating_sub(last) > self.config.lift_timeout_ms as u64 * 1000
+        });
+        if lifted {
+            self.lift();
+        }
+        let data = data?;
+        self.last_report_us = Some(timestamp_us);
+
+        if data.buttons() != 0 {
+            return Some(data);
+        }
+
+        self.touch(data)
+    }
+}
+
+impl<const N: usize> SoftLanding<AbsoluteData, N> {
+    /// Process an absolute report, returning the report to use
+    /// Reports without a touch are passed through and end the contact.
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<AbsoluteData> {
+        if data.z_level == 0 {
+            self.lift();
+            return Some(*data);
+        }
+        if data.button_state != 0 {
+            return Some(*data);
+        }
+
+        self.touch(*data)
+    }
+}
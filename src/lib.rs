@@ -40,27 +40,175 @@
 //! [Pinnacle ASIC documentation]: https://static1.squarespace.com/static/53233e4be4b044fa7626c453/t/599de7856f4ca3c38aa74632/1503520647200/gt-an-090620_2-4_interfacingtopinnacle_i2c-spi_docver1-6.pdf
 
 #![no_std]
+// Helpers shared with the disabled position mode stay compiled, but are never linked in
+#![cfg_attr(not(all(feature = "absolute", feature = "relative")), allow(dead_code))]
 
-use core::{fmt::Debug, marker::PhantomData};
+#[cfg(not(any(feature = "absolute", feature = "relative")))]
+compile_error!("At least one of the `absolute` and `relative` features must be enabled");
+
+use core::{convert::Infallible, fmt::Debug, marker::PhantomData};
 
 use config::{Bitfield, Mask};
 use embedded_hal::{
+    delay::DelayNs,
     digital::{self, InputPin},
     i2c::I2c,
 };
-
-use crate::register::{Bank0, Register};
+#[cfg(any(feature = "absolute", feature = "relative"))]
+use heapless::Vec;
+
+#[cfg(feature = "eh0")]
+pub use crate::eh0::{Eh0Error, Eh0I2c, Eh0Pin};
+#[cfg(feature = "float")]
+pub use crate::float::{Ballistics, Velocity};
+#[cfg(feature = "metrics")]
+pub use crate::metrics::Metrics;
+#[cfg(feature = "midi")]
+pub use crate::midi::{CcMapping, CcMessage, CcSource, GestureCc, MidiMapper};
+#[cfg(feature = "notify")]
+pub use crate::notify::{DataReadyNotifier, NotifiedPin};
+#[cfg(feature = "absolute")]
+use crate::packet::AbsolutePacket;
+#[cfg(feature = "relative")]
+use crate::packet::RelativePacket;
+#[cfg(feature = "hil-tests")]
+pub use crate::selftest::{SelfTest, SelfTestReport};
+#[cfg(feature = "trajectory")]
+pub use crate::trajectory::{Shape, Trajectory};
 pub use crate::{
+    anymeas::AnyMeasConfig,
+    attach::Attached,
+    budget::Budgeted,
+    bus::{BusHealth, BusProfile},
+    button::RelativeEvent,
+    calibration::{Calibration, Corner},
+    clock::{Clock, StaleReportPolicy},
+    compensation::{
+        AdcAttenuation, CompensationFlags, TemperatureCompensation, TemperatureSource, Thresholds,
+    },
     config::{
-        Address, FeedMode, FilterMode, GlideExtendMode, IntelliMouseMode, PositionMode, PowerMode,
-        ScrollMode, TapMode, XYEnable, XYInverted, XYSwapped,
+        Address, FeedMode, FilterMode, GlideExtendMode, I2cAddress, IntelliMouseMode, PositionMode,
+        PowerMode, ScrollMode, TapMode, XYEnable, XYInverted, XYSwapped,
     },
-    error::Error,
+    config_audit::ConfigAudit,
+    data_ready::{DataReadySignal, DataReadySource, ErasedPin, PinErrorPolicy},
+    drag::{DragConfig, DragRelease, TapDrag},
+    error::{ErasedError, Error, PinFault, SensorError},
+    events::{Event, EventPriority, EventQueue, EventSink},
+    force::{ForceClick, ForceConfig, ForceEvent},
+    frame_sync::FrameSync,
+    functional_test::{CheckResult, TestReport},
+    gesture::{Gesture, GestureConfig, GestureEngine, SwipeDirection, TapLocation},
+    haptics::{Edge, HapticHooks},
+    integrate::MotionIntegrator,
+    landing::{SoftLanding, SoftLandingConfig},
+    matrix::{CalibrationData, CALIBRATION_DATA_LEN, COMPENSATION_MATRIX_LEN},
+    mode_check::ModeCheck,
+    multi_finger::MultiFingerConfig,
+    orientation::{CoordinateOrigin, MountingOrientation, NotCentered},
+    packet::PacketFormat,
+    polar::PolarPosition,
+    power::{AutoPower, AutoPowerConfig, PowerHooks, PowerLevel},
+    presence::{TouchPresence, TouchPresencePin},
+    probe::ProbeReport,
+    profile::{LatencyProfile, OverlayMaterial, OverlayThickness},
+    proxy::ConfigProxy,
+    recording::{RecordedReport, Recorder, Replayer},
+    rim::{RimConfig, RimDetector, RimGesture},
+    scroll::ScrollConfig,
+    slider::{Lane, LaneSplit, Slider, SliderAxis, SliderConfig, SliderPair, SliderPairConfig},
+    speed::SpeedPreset,
+    timings::Timings,
+    trackpad::{AbsoluteTrackpad, RelativeTrackpad, Trackpad},
+    trackpad_config::{ConfigField, ConfigMismatch, ConfigRegister, TrackpadConfig},
+    transform::Transform,
+    validation::{InvalidPacketAction, PacketValidation},
+    variant::{Module, Variant},
+    zones::{Region, TapZone, TapZones, ZoneConfig, ZoneEvent},
+};
+use crate::{
+    button::ButtonDecoupler,
+    clock::ReportAge,
+    multi_finger::MultiFingerDetector,
+    packet::Packet,
+    register::{Bank0, Register},
+    state::{BusState, ConfigShadows, Hooks, Pipeline, PowerState},
+    traffic::TrafficMeter,
 };
 
+mod anymeas;
+#[cfg(feature = "async")]
+pub mod asynch;
+mod attach;
+mod budget;
+mod bus;
+mod button;
+mod calibration;
+mod clock;
+mod compensation;
+mod confidence;
 mod config;
+mod config_audit;
+mod data_ready;
+mod drag;
+#[cfg(feature = "eh0")]
+mod eh0;
+pub mod era;
 mod error;
+mod events;
+#[cfg(test)]
+mod fake_bus;
+#[cfg(feature = "float")]
+mod float;
+mod force;
+mod frame_sync;
+mod functional_test;
+mod gesture;
+mod haptics;
+mod integrate;
+#[cfg(feature = "labels")]
+mod labels;
+mod landing;
+pub mod ll;
+mod matrix;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "midi")]
+mod midi;
+mod mode_check;
+pub mod modules;
+mod multi_finger;
+#[cfg(feature = "notify")]
+mod notify;
+mod orientation;
+mod packet;
+mod polar;
+mod power;
+mod presence;
+mod probe;
+mod profile;
+mod proxy;
+mod recording;
 mod register;
+mod rim;
+mod scroll;
+#[cfg(feature = "hil-tests")]
+mod selftest;
+mod slider;
+mod speed;
+mod state;
+mod timings;
+mod trackpad;
+mod trackpad_config;
+mod traffic;
+#[cfg(feature = "trajectory")]
+mod trajectory;
+pub mod transform;
+mod validation;
+mod variant;
+#[cfg(feature = "wire")]
+pub mod wire;
+mod zones;
 
 mod private {
 
@@ -71,9 +219,31 @@ const PINNACLE_X_LOWER: u16 = 128;
 const PINNACLE_Y_LOWER: u16 = 64;
 const PINNACLE_X_UPPER: u16 = 1920;
 const PINNACLE_Y_UPPER: u16 = 1472;
+/// Highest X position the touchpad reports in absolute mode
+const PINNACLE_X_MAX: u16 = 2047;
+/// Highest Y position the touchpad reports in absolute mode
+const PINNACLE_Y_MAX: u16 = 1535;
+
+/// Bit of the first relative packet byte that is always set
+const RELATIVE_BYTE0_ALWAYS_SET: u8 = 0b0000_1000;
+
+/// Firmware ID reported by Pinnacle ASICs
+const PINNACLE_FIRMWARE_ID: u8 = 0x07;
+
+/// Software data ready flag in the STATUS1 register
+const STATUS1_SW_DR: u8 = 0b0000_0100;
+/// Number of registers from STATUS1 through PACKET_BYTE5, read in one burst by combined reads
+const COMBINED_READ_LEN: usize = 22;
+/// Offset of PACKET_BYTE0 in a combined read
+const COMBINED_PACKET_OFFSET: usize = 16;
+/// Command complete flag in the STATUS1 register
+const STATUS1_SW_CC: u8 = 0b0000_1000;
+
+/// Calibration trigger flag in the CAL_CONFIG1 register, cleared by the touchpad when done
+const CAL_CONFIG1_CALIBRATE: u8 = 0b0000_0001;
 
 /// Position and button data in relative mode
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct RelativeData {
     /// Whether the primary button is pressed (tap)
     pub primary_pressed: bool,
@@ -85,10 +255,20 @@ pub struct RelativeData {
     pub x_delta: i16,
     /// The relative delta in the Y dimension
     pub y_delta: i16,
+    /// Scroll wheel detents, only reported with IntelliMouse mode enabled, see `ScrollConfig`
+    pub wheel: i8,
+    /// The axes enabled when the report was read, deltas of disabled axes are meaningless
+    pub axes: XYEnable,
+    /// Upper bound of the report age in microseconds, `None` if no clock is set
+    pub age_us: Option<u32>,
+    /// Whether the report is older than the configured maximum report age
+    pub stale: bool,
+    /// Sequence number of the report, increasing by one (wrapping) with every returned report
+    pub sequence: u32,
 }
 
 /// Position and button data in absolute mode
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub struct AbsoluteData {
     /// The current button state encoded as bits (lowest 6 bits are used)
     pub button_state: u8,
@@ -99,6 +279,160 @@ pub struct AbsoluteData {
     pub y_pos: u16,
     /// Z-level (0 when no finger is close, increases as finger approaches)
     pub z_level: u8,
+    /// Z-level smoothed like the position, see `set_position_smoothing()`, equal to `z_level`
+    /// without smoothing
+    pub z_filtered: u8,
+    /// Confidence in the touch in percent, from its Z level, stability and smoothing
+    /// residual, e.g. to weight or ignore marginal touches near the sensing threshold.
+    /// 0 without a touch.
+    pub confidence: u8,
+    /// The axes enabled when the report was read, positions of disabled axes are meaningless
+    pub axes: XYEnable,
+    /// Whether more than one finger probably touches the pad, making the position unreliable.
+    /// Only detected if multi-finger detection is enabled.
+    pub multi_finger: bool,
+    /// The position in polar coordinates, only reported if enabled with `set_polar_center()`
+    pub polar: Option<PolarPosition>,
+    /// Signed X/Y offsets from the center of the usable area, only reported with
+    /// `CoordinateOrigin::Center`
+    pub center_offset: Option<(i16, i16)>,
+    /// Upper bound of the report age in microseconds, `None` if no clock is set
+    pub age_us: Option<u32>,
+    /// Whether the report is older than the configured maximum report age
+    pub stale: bool,
+    /// Sequence number of the report, increasing by one (wrapping) with every returned report
+    pub sequence: u32,
+}
+
+impl RelativeData {
+    /// Create a report with the given deltas, no buttons pressed and all axes enabled
+    pub const fn new(x_delta: i16, y_delta: i16) -> Self {
+        Self {
+            primary_pressed: false,
+            secondary_pressed: false,
+            aux_pressed: false,
+            x_delta,
+            y_delta,
+            wheel: 0,
+            axes: XYEnable::Enabled,
+            age_us: None,
+            stale: false,
+            sequence: 0,
+        }
+    }
+
+    /// The delta in the X dimension, `None` if the X axis is disabled
+    pub fn x(&self) -> Option<i16> {
+        self.axes.x_enabled().then_some(self.x_delta)
+    }
+
+    /// The delta in the Y dimension, `None` if the Y axis is disabled
+    pub fn y(&self) -> Option<i16> {
+        self.axes.y_enabled().then_some(self.y_delta)
+    }
+
+    /// The pressed buttons as HID mouse button bits: primary, secondary and aux in bits 0-2
+    pub fn buttons(&self) -> u8 {
+        self.primary_pressed as u8
+            | (self.secondary_pressed as u8) << 1
+            | (self.aux_pressed as u8) << 2
+    }
+}
+
+/// X/Y deltas, 0 for disabled axes
+impl From<RelativeData> for (i16, i16) {
+    fn from(data: RelativeData) -> Self {
+        (data.x().unwrap_or(0), data.y().unwrap_or(0))
+    }
+}
+
+/// X/Y deltas saturated to `i8`, as in boot protocol mouse reports, 0 for disabled axes
+impl From<RelativeData> for (i8, i8) {
+    fn from(data: RelativeData) -> Self {
+        let (x, y): (i16, i16) = data.into();
+        (saturate_i8(x), saturate_i8(y))
+    }
+}
+
+/// A boot protocol mouse report: buttons, X/Y deltas saturated to `i8` and wheel
+impl From<RelativeData> for (u8, i8, i8, i8) {
+    fn from(data: RelativeData) -> Self {
+        let (x, y): (i8, i8) = data.into();
+        (data.buttons(), x, y, data.wheel)
+    }
+}
+
+fn saturate_i8(value: i16) -> i8 {
+    value.clamp(i8::MIN as i16, i8::MAX as i16) as i8
+}
+
+impl AbsoluteData {
+    /// Create a report at the given position, no buttons pressed and all axes enabled
+    pub const fn new(x_pos: u16, y_pos: u16, z_level: u8) -> Self {
+        Self {
+            button_state: 0,
+            x_pos,
+            y_pos,
+            z_level,
+            z_filtered: z_level,
+            confidence: 0,
+            axes: XYEnable::Enabled,
+            multi_finger: false,
+            polar: None,
+            center_offset: None,
+            age_us: None,
+            stale: false,
+            sequence: 0,
+        }
+    }
+
+    /// The position in the X dimension, `None` if the X axis is disabled
+    pub fn x(&self) -> Option<u16> {
+        self.axes.x_enabled().then_some(self.x_pos)
+    }
+
+    /// The position in the Y dimension, `None` if the Y axis is disabled
+    pub fn y(&self) -> Option<u16> {
+        self.axes.y_enabled().then_some(self.y_pos)
+    }
+
+    /// The signed offset from the center in the X dimension, `None` if the X axis is disabled
+    /// or the origin is not `CoordinateOrigin::Center`
+    pub fn signed_x(&self) -> Option<i16> {
+        let (x, _) = self.center_offset?;
+        self.axes.x_enabled().then_some(x)
+    }
+
+    /// The signed offset from the center in the Y dimension, `None` if the Y axis is disabled
+    /// or the origin is not `CoordinateOrigin::Center`
+    pub fn signed_y(&self) -> Option<i16> {
+        let (_, y) = self.center_offset?;
+        self.axes.y_enabled().then_some(y)
+    }
+}
+
+/// X/Y position, as reported regardless of disabled axes
+impl From<AbsoluteData> for (u16, u16) {
+    fn from(data: AbsoluteData) -> Self {
+        (data.x_pos, data.y_pos)
+    }
+}
+
+/// X/Y position and Z level, as reported regardless of disabled axes
+impl From<AbsoluteData> for (u16, u16, u8) {
+    fn from(data: AbsoluteData) -> Self {
+        (data.x_pos, data.y_pos, data.z_level)
+    }
+}
+
+/// Signed X/Y offsets from the center, as reported regardless of disabled axes
+/// Fails for reports not read with `CoordinateOrigin::Center`.
+impl TryFrom<AbsoluteData> for (i16, i16) {
+    type Error = NotCentered;
+
+    fn try_from(data: AbsoluteData) -> Result<Self, Self::Error> {
+        data.center_offset.ok_or(NotCentered)
+    }
 }
 
 pub trait FeedState: private::Sealed {}
@@ -109,23 +443,60 @@ impl private::Sealed for FeedEnabled {}
 impl FeedState for NoFeed {}
 impl private::Sealed for NoFeed {}
 
-pub trait PositionReportingMode: private::Sealed {}
+pub trait PositionReportingMode: private::Sealed {
+    /// The position mode the touchpad is configured with in this state
+    const MODE: PositionMode;
+}
+/// Relative position mode, also the initial state of every driver. Without the `relative`
+/// feature, switch to absolute mode with `enable_absolute()`.
 pub struct Relative;
+/// Absolute position mode, requires the `absolute` feature
+#[cfg(feature = "absolute")]
 pub struct Absolute;
-impl PositionReportingMode for Relative {}
+impl PositionReportingMode for Relative {
+    const MODE: PositionMode = PositionMode::Relative;
+}
 impl private::Sealed for Relative {}
-impl PositionReportingMode for Absolute {}
+#[cfg(feature = "absolute")]
+impl PositionReportingMode for Absolute {
+    const MODE: PositionMode = PositionMode::Absolute;
+}
+#[cfg(feature = "absolute")]
 impl private::Sealed for Absolute {}
 
-pub struct Tm040040<'a, I2C, PositionMode: PositionReportingMode, Feed: FeedState, E> {
-    i2c: I2C,
-    address: Address,
-    hardware_data_ready: &'a mut dyn InputPin<Error = E>,
+/// Driver core shared by all Pinnacle touchpad modules, see the `Tm040040`, `Tm035035` and
+/// `Tm023023` front-ends.
+pub struct Pinnacle<'a, I2C, M, PositionMode: PositionReportingMode, Feed: FeedState, E> {
+    ll: ll::Pinnacle<I2C>,
+    bus: BusState<'a, E>,
+    pipeline: Pipeline<'a>,
+    power: PowerState,
+    shadows: ConfigShadows,
+    hooks: Hooks<'a>,
+    #[cfg(feature = "metrics")]
+    metrics: Metrics,
     _pos_state: PhantomData<PositionMode>,
     _feed_state: PhantomData<Feed>,
+    _module: PhantomData<M>,
 }
 
-impl<I2C, E, PosMode, Feed, PinError> Tm040040<'_, I2C, PosMode, Feed, PinError>
+/// Result of a typestate transition
+type Transition<'a, I2C, M, PosMode, Feed, E, PinError> =
+    Result<Pinnacle<'a, I2C, M, PosMode, Feed, PinError>, Error<E, PinError>>;
+
+/// Driver for the 40mm TM040040 touchpad
+pub type Tm040040<'a, I2C, PosMode, Feed, PinError> =
+    Pinnacle<'a, I2C, modules::Tm040040, PosMode, Feed, PinError>;
+
+/// Driver for the 35mm TM035035 touchpad
+pub type Tm035035<'a, I2C, PosMode, Feed, PinError> =
+    Pinnacle<'a, I2C, modules::Tm035035, PosMode, Feed, PinError>;
+
+/// Driver for the 23mm TM023023 touchpad
+pub type Tm023023<'a, I2C, PosMode, Feed, PinError> =
+    Pinnacle<'a, I2C, modules::Tm023023, PosMode, Feed, PinError>;
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -135,7 +506,14 @@ where
 {
     /// Return the underlying I2C instance for reuse
     pub fn free(self) -> I2C {
-        self.i2c
+        self.ll.free()
+    }
+
+    /// Get the low-level register interface, for things the high-level API doesn't cover
+    /// Changes made through it aren't tracked by the driver, e.g. switching the feed or
+    /// position mode leaves the typestate out of sync with the touchpad.
+    pub fn ll(&mut self) -> &mut ll::Pinnacle<I2C> {
+        &mut self.ll
     }
 
     /// Get the device/firmware ID of the touchpad
@@ -143,128 +521,1078 @@ where
         self.read_reg(&Bank0::FIRMWARE_ID)
     }
 
+    /// Get the firmware version of the touchpad
+    pub fn firmware_version(&mut self) -> Result<u8, Error<E, PinError>> {
+        self.read_reg(&Bank0::FIRMWARE_VERSION)
+    }
+
     /// Get the currently configured power mode
     pub fn power_mode(&mut self) -> Result<PowerMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::SYS_CONFIG1)? >> 1;
-        let mode = PowerMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set the power mode
     pub fn set_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E, PinError>> {
-        self.update_reg(power_mode)
+        self.switch_power_mode(power_mode)?;
+        self.emit(Event::PowerMode(power_mode));
+
+        Ok(())
+    }
+
+    /// Write the power mode, calling the power hooks around the transition
+    fn switch_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Error<E, PinError>> {
+        let from = self.power.last_power_mode;
+        let transition = from != Some(power_mode);
+        if let Some(hooks) = self.hooks.power_hooks.as_mut().filter(|_| transition) {
+            hooks.before_transition(from, power_mode);
+        }
+        self.update_reg(power_mode)?;
+        self.power.sleeping = power_mode == PowerMode::Sleep;
+        self.power.last_power_mode = Some(power_mode);
+        if let Some(hooks) = self.hooks.power_hooks.as_mut().filter(|_| transition) {
+            hooks.after_transition(from, power_mode);
+        }
+
+        Ok(())
     }
 
     /// Get the current feed mode
     pub fn feed_mode(&mut self) -> Result<FeedMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & FeedMode::BITMASK;
-        let mode = FeedMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set the feed mode, enabling or disabling position reporting
     fn set_feed_mode(&mut self, fd: FeedMode) -> Result<(), Error<E, PinError>> {
-        self.update_reg(fd)
+        self.update_reg(fd)?;
+        self.emit(Event::FeedMode(fd));
+
+        Ok(())
     }
 
     /// Get the current position reporting mode
     pub fn position_mode(&mut self) -> Result<PositionMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & PositionMode::BITMASK;
-        let mode = PositionMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set the current position reporting mode (Absolute or Relative coordinates)
     fn set_position_mode(&mut self, pos: PositionMode) -> Result<(), Error<E, PinError>> {
-        self.update_reg(pos)
+        self.update_reg(pos)?;
+        self.emit(Event::PositionMode(pos));
+
+        Ok(())
     }
 
     /// Get the current filter mode
     pub fn filter_mode(&mut self) -> Result<FilterMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & FilterMode::BITMASK;
-        let mode = FilterMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     ///Set the hardware filter mode
+    /// Disabling the hardware filter enables the software position smoothing recommended by
+    /// `LatencyProfile::Balanced` if no smoothing is set, unless opted out with
+    /// `set_auto_software_filter()`.
     pub fn set_filter_mode(&mut self, filter: FilterMode) -> Result<(), Error<E, PinError>> {
-        self.update_reg(filter)
+        self.update_reg(filter)?;
+        if filter == FilterMode::Disable
+            && self.pipeline.auto_software_filter
+            && self.pipeline.position_smoothing == 0
+        {
+            self.pipeline.position_smoothing = LatencyProfile::Balanced.smoothing();
+        }
+
+        Ok(())
     }
 
     /// Get enabled axis
     pub fn xy_enable(&mut self) -> Result<XYEnable, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & XYEnable::BITMASK;
-        let mode = XYEnable::try_from(bits)?;
+        self.shadows.xy_enable = self.read_field()?;
 
-        Ok(mode)
+        Ok(self.shadows.xy_enable)
     }
 
     /// Set enabled axis
+    /// Reports carry the enabled axes, so values of disabled axes can be told apart from no motion.
     pub fn set_xy_enable(&mut self, yx: XYEnable) -> Result<(), Error<E, PinError>> {
-        self.update_reg(yx)
+        self.update_reg(yx)?;
+        self.shadows.xy_enable = yx;
+
+        Ok(())
     }
 
     /// Get axis inversion setting
+    /// In absolute mode, inversion is applied in software and the configured setting is returned.
     pub fn xy_inverted(&mut self) -> Result<XYInverted, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & XYInverted::BITMASK;
-        let mode = XYInverted::try_from(bits)?;
-
-        Ok(mode)
+        match PosMode::MODE {
+            PositionMode::Relative => self.read_field(),
+            PositionMode::Absolute => Ok(self.pipeline.orientation.inverted()),
+        }
     }
 
     /// Invert axis
+    /// This changes the inversion part of the mounting orientation, see `set_orientation()`.
     pub fn set_xy_inverted(&mut self, yx: XYInverted) -> Result<(), Error<E, PinError>> {
-        self.update_reg(yx)
+        self.set_orientation(MountingOrientation::from_flags(
+            self.pipeline.orientation.swapped(),
+            yx,
+        ))
+    }
+
+    /// Get the configured mounting orientation
+    pub fn orientation(&self) -> MountingOrientation {
+        self.pipeline.orientation
+    }
+
+    /// Set the mounting orientation, configuring axis swapping and inversion accordingly
+    /// In relative mode, this is done by the touchpad. Hardware swapping and inversion don't
+    /// apply to absolute data on all firmware revisions, so in absolute mode they are turned off
+    /// and applied in software instead. The orientation is kept when switching modes.
+    pub fn set_orientation(
+        &mut self,
+        orientation: MountingOrientation,
+    ) -> Result<(), Error<E, PinError>> {
+        self.pipeline.orientation = orientation;
+        self.apply_orientation()?;
+        self.emit(Event::Orientation(orientation));
+
+        Ok(())
+    }
+
+    /// Configure hardware swapping and inversion for the current orientation and position mode
+    fn apply_orientation(&mut self) -> Result<(), Error<E, PinError>> {
+        let (swapped, inverted) = match PosMode::MODE {
+            PositionMode::Relative => (
+                self.pipeline.orientation.swapped(),
+                self.pipeline.orientation.inverted(),
+            ),
+            PositionMode::Absolute => (XYSwapped::Normal, XYInverted::Normal),
+        };
+        self.update_reg(swapped)?;
+        self.update_reg(inverted)
+    }
+
+    /// Force a calibration of the touchpad and wait for it to complete
+    /// Feed is disabled while calibrating and restored afterwards. The finger should not touch
+    /// the pad during calibration.
+    pub fn calibrate(&mut self, delay: &mut impl DelayNs) -> Result<(), Error<E, PinError>> {
+        self.calibrate_timed(delay).map(|_| ())
+    }
+
+    /// Calibrate like `calibrate()`, returning the time the calibration took in milliseconds
+    fn calibrate_timed(&mut self, delay: &mut impl DelayNs) -> Result<u32, Error<E, PinError>> {
+        self.start_calibration()?;
+
+        let mut elapsed_ms = 0;
+        while !self.poll_calibration()? {
+            if elapsed_ms >= self.bus.timings.calibration_timeout_ms {
+                if let Some(feed) = self.power.calibrating.take() {
+                    self.set_feed_mode(feed)?;
+                }
+                return Err(Error::SensorError(error::SensorError::CalibrationTimeout));
+            }
+            delay.delay_ms(1);
+            elapsed_ms += 1;
+        }
+
+        Ok(elapsed_ms)
+    }
+
+    /// Start a calibration without waiting for it to complete
+    /// Reads return no data while calibrating instead of garbage, the calibration is completed
+    /// by the first read or `poll_calibration()` after the touchpad finished it.
+    pub fn start_calibration(&mut self) -> Result<(), Error<E, PinError>> {
+        if self.power.calibrating.is_some() {
+            return Ok(());
+        }
+        let feed: FeedMode = self.read_field()?;
+        self.set_feed_mode(FeedMode::NoFeed)?;
+
+        let cal_config = self.read_reg(&Bank0::CAL_CONFIG1)?;
+        self.write_reg(&Bank0::CAL_CONFIG1, cal_config | CAL_CONFIG1_CALIBRATE)?;
+        self.power.calibrating = Some(feed);
+
+        Ok(())
+    }
+
+    /// Check whether the calibration started with `start_calibration()` finished, restoring
+    /// feed if so
+    /// Returns `true` if no calibration is in progress anymore.
+    pub fn poll_calibration(&mut self) -> Result<bool, Error<E, PinError>> {
+        let Some(feed) = self.power.calibrating else {
+            return Ok(true);
+        };
+        if self.read_reg(&Bank0::CAL_CONFIG1)? & CAL_CONFIG1_CALIBRATE != 0 {
+            return Ok(false);
+        }
+
+        self.power.calibrating = None;
+        self.set_feed_mode(feed)?;
+        self.clear_flags()?;
+        self.pipeline.discard_remaining = self.pipeline.discard_reports;
+        self.emit(Event::Calibrated);
+
+        Ok(true)
+    }
+
+    /// Whether a calibration started with `start_calibration()` is in progress
+    pub fn is_calibrating(&self) -> bool {
+        self.power.calibrating.is_some()
+    }
+
+    /// Shut the touchpad down, remembering its configuration for `resume()`
+    /// Does nothing if the touchpad is already suspended.
+    pub fn suspend(&mut self) -> Result<(), Error<E, PinError>> {
+        if self.power.suspended.is_some() {
+            return Ok(());
+        }
+        let config = self.config()?;
+        self.set_power_mode(PowerMode::Shutdown)?;
+        self.power.suspended = Some(config);
+        if let Some(presence) = self.hooks.touch_presence {
+            presence.set(false);
+        }
+        self.emit(Event::Suspended);
+
+        Ok(())
+    }
+
+    /// Wake the touchpad up after `suspend()` and reapply the remembered configuration
+    /// This also restores the configuration if the touchpad lost power while suspended.
+    /// Does nothing if the touchpad isn't suspended.
+    pub fn resume(&mut self, delay: &mut impl DelayNs) -> Result<(), Error<E, PinError>> {
+        let Some(config) = self.power.suspended else {
+            return Ok(());
+        };
+        self.set_power_mode(PowerMode::Normal)?;
+        delay.delay_ms(self.bus.timings.wake_ms);
+
+        self.apply_config(&config)?;
+        self.power.suspended = None;
+        self.pipeline.last_absolute = None;
+
+        self.clear_flags()?;
+        self.pipeline.discard_remaining = self.pipeline.discard_reports;
+        self.emit(Event::Resumed);
+
+        Ok(())
+    }
+
+    /// Whether the touchpad is suspended
+    pub fn is_suspended(&self) -> bool {
+        self.power.suspended.is_some()
+    }
+
+    /// Get the performance metrics collected so far
+    #[cfg(feature = "metrics")]
+    pub fn metrics(&self) -> Metrics {
+        self.metrics
+    }
+
+    /// Reset the collected performance metrics
+    #[cfg(feature = "metrics")]
+    pub fn reset_metrics(&mut self) {
+        self.metrics = Metrics::default();
+    }
+
+    /// Get how the hardware data ready line is interpreted
+    pub fn data_ready_signal(&self) -> DataReadySignal {
+        self.bus.dr_signal
+    }
+
+    /// Get the bus communication profile
+    pub fn bus_profile(&self) -> BusProfile {
+        self.bus.bus_profile
+    }
+
+    /// Get the bus error statistics collected so far
+    pub fn health(&self) -> BusHealth {
+        self.bus.bus_health
+    }
+
+    /// Continue counting from previously persisted bus error statistics, to count over the
+    /// lifetime of the device
+    pub fn restore_health(&mut self, health: BusHealth) {
+        self.bus.bus_health = health;
+    }
+
+    /// Set the bus communication profile, e.g. `BusProfile::LongCable` for slow buses with
+    /// long wiring
+    pub fn set_bus_profile(&mut self, profile: BusProfile) {
+        self.bus.bus_profile = profile;
+    }
+
+    /// Set how the hardware data ready line is interpreted
+    /// Use `DataReadySignal::Edge` if the DR line is shared with other devices
+    pub fn set_data_ready_signal(&mut self, signal: DataReadySignal) {
+        self.bus.dr_signal = signal;
+    }
+
+    /// Enable/disable re-checking the hardware data ready line after the status flags were cleared
+    /// If the line is still asserted, a new report is already pending, see `data_pending()`
+    pub fn set_dr_recheck(&mut self, recheck: bool) {
+        self.bus.dr_recheck = recheck;
+    }
+
+    /// Enable/disable verifying that the hardware data ready line deasserts when the status
+    /// flags are cleared
+    /// If the line stays high without a new report pending, `clear_flags()` returns
+    /// `Error::HandshakeFailed`, pointing to a line that is shorted or stuck high. With a level
+    /// signal this is what keeps the driver from reading the same line forever; with an edge
+    /// signal on a shared line, another touchpad holding the line fails the handshake, so only
+    /// enable it for lines with a single touchpad.
+    pub fn set_dr_handshake(&mut self, handshake: bool) {
+        self.bus.dr_handshake = handshake;
+    }
+
+    /// Whether a report was found to be pending right after the last one was read
+    /// Only tracked if `set_dr_recheck(true)` was set. No new edge will be signaled for such a
+    /// report, so it should be read right away.
+    pub fn data_pending(&self) -> bool {
+        self.bus.dr_pending
+    }
+
+    /// Set what to do when reading the hardware data ready line fails
+    pub fn set_pin_error_policy(&mut self, policy: PinErrorPolicy) {
+        self.bus.pin_error_policy = policy;
+    }
+
+    /// Enable/disable deferring the clearing of the status flags after a report was read
+    /// The flags are then cleared on the next read attempt or by calling `clear_flags()`, so
+    /// registers can be read after a report without the touchpad generating the next one.
+    /// Clearing them on the next read attempt also re-checks the data ready line, see
+    /// `set_dr_recheck()`.
+    pub fn set_lazy_flag_clearing(&mut self, lazy: bool) {
+        self.bus.lazy_clear = lazy;
+    }
+
+    /// Enable/disable reading the status and the packet in one burst
+    /// Reports are then checked for and read with a single transaction from STATUS1 through
+    /// PACKET_BYTE5, which avoids a race between checking the status and reading the packet.
+    /// Without a hardware data ready line, every poll without a report reads the full burst.
+    pub fn set_combined_reads(&mut self, combined: bool) {
+        self.bus.combined_reads = combined;
+    }
+
+    /// Set the sample rate (raw value of the sample rate register) to switch to when the data
+    /// ready line asserts while the touchpad sleeps, `None` to disable
+    /// The touchpad is then put into normal mode before the report is read, instead of only
+    /// leaving sleep mode on its own after the first touch. This shaves latency off the first
+    /// movement after idling. Requires a hardware data ready line.
+    pub fn set_wake_boost(&mut self, sample_rate: Option<u8>) {
+        self.power.wake_boost = sample_rate;
+    }
+
+    /// Set the number of reports discarded after enabling feed, calibrating or resuming, as the
+    /// first reports are often garbage
+    pub fn set_discard_reports(&mut self, count: u8) {
+        self.pipeline.discard_reports = count;
+    }
+
+    /// Get the number of reports discarded after enabling feed, calibrating or resuming
+    pub fn discard_reports(&self) -> u8 {
+        self.pipeline.discard_reports
+    }
+
+    /// Whether the report just read is one of the reports to discard
+    fn discard_report(&mut self) -> bool {
+        if self.pipeline.discard_remaining == 0 {
+            return false;
+        }
+        self.pipeline.discard_remaining -= 1;
+
+        true
+    }
+
+    /// Smooth an absolute position and Z level, restarting with every touch
+    fn smooth_position(&mut self, x: u16, y: u16, z_level: u8) -> (u16, u16, u8) {
+        if z_level == 0 || self.pipeline.position_smoothing == 0 {
+            self.pipeline.smoothed_position = None;
+            return (x, y, z_level);
+        }
+        let weight = self.pipeline.position_smoothing as u32;
+        let smooth = |previous: u16, current: u16| {
+            ((previous as u32 * weight + current as u32 * (256 - weight)) / 256) as u16
+        };
+        let smoothed = match self.pipeline.smoothed_position {
+            Some((last_x, last_y, last_z)) => (
+                smooth(last_x, x),
+                smooth(last_y, y),
+                smooth(last_z as u16, z_level as u16) as u8,
+            ),
+            None => (x, y, z_level),
+        };
+        self.pipeline.smoothed_position = Some(smoothed);
+
+        smoothed
+    }
+
+    /// Whether the hardware data ready line currently can't be read and the software data ready
+    /// flag is polled instead, see `PinErrorPolicy::PollSoftware`
+    pub fn is_dr_degraded(&self) -> bool {
+        self.bus.dr_degraded
+    }
+
+    /// Set the maximum age of reports in microseconds, `None` to disable age checks
+    /// Requires a clock to be set, see `set_clock()`.
+    pub fn set_max_report_age(&mut self, max_age_us: Option<u32>) {
+        self.bus.max_report_age_us = max_age_us;
+    }
+
+    /// Set how reports older than the maximum report age are handled
+    pub fn set_stale_report_policy(&mut self, policy: StaleReportPolicy) {
+        self.bus.stale_policy = policy;
+    }
+
+    /// Set the plausibility checks applied to packets, `None` to disable validation
+    pub fn set_packet_validation(&mut self, validation: Option<PacketValidation>) {
+        self.pipeline.validation = validation;
+    }
+
+    /// Set the detection of the touchpad reporting in a different position mode than expected,
+    /// `None` to disable it
+    pub fn set_mode_check(&mut self, check: Option<ModeCheck>) {
+        self.shadows.mode_check = check;
+        self.shadows.reports_since_mode_check = 0;
+    }
+
+    /// Set the periodic read-back of the feed configuration registers, `None` to disable it
+    pub fn set_config_audit(&mut self, audit: Option<ConfigAudit>) {
+        self.shadows.config_audit = audit;
+        self.shadows.reports_since_audit = 0;
+        self.shadows.last_audit_us = None;
+    }
+
+    /// Handle a packet that failed validation according to the configured action
+    fn invalid_packet<T>(&mut self) -> Result<Option<T>, Error<E, PinError>> {
+        #[cfg(feature = "metrics")]
+        self.metrics.record_dropped_report();
+
+        match self.pipeline.validation.map(|validation| validation.action) {
+            Some(InvalidPacketAction::Drop) => Ok(None),
+            _ => Err(Error::InvalidPacket),
+        }
+    }
+
+    /// Enable flagging of reports that probably stem from more than one finger, `None` to disable
+    /// Only applies to absolute mode.
+    pub fn set_multi_finger_detection(&mut self, config: Option<MultiFingerConfig>) {
+        self.pipeline.multi_finger = config.map(MultiFingerDetector::new);
+    }
+
+    /// Report button changes as separate events without motion, see `relative_event()`
+    /// Only applies to relative mode.
+    pub fn set_button_decoupling(&mut self, enabled: bool) {
+        self.pipeline.button_decoupler = enabled.then(ButtonDecoupler::default);
+    }
+
+    /// Enable or disable accounting of the bytes transferred on the bus, see `bus_traffic()`
+    /// Accounting needs a clock. Disabling it also removes the traffic budget.
+    pub fn set_traffic_accounting(&mut self, enabled: bool) {
+        self.bus.traffic = enabled.then(TrafficMeter::default);
+    }
+
+    /// Set a budget of bytes per second for the bus, `None` to remove it
+    /// This enables traffic accounting. Once the budget for the current second is used up,
+    /// optional reads are skipped: the status flag confirming the data ready line, the
+    /// button byte of absolute reports and the wheel byte of relative reports.
+    pub fn set_traffic_budget(&mut self, bytes_per_s: Option<u32>) {
+        self.bus
+            .traffic
+            .get_or_insert_with(TrafficMeter::default)
+            .set_budget(bytes_per_s);
+    }
+
+    /// Get the traffic budget in bytes per second
+    pub fn traffic_budget(&self) -> Option<u32> {
+        self.bus.traffic.and_then(|traffic| traffic.budget())
+    }
+
+    /// Bytes per second transferred on the bus during the last full second, `None` if traffic
+    /// isn't accounted or no second has passed yet
+    /// Only payload bytes are counted, not address bytes.
+    pub fn bus_traffic(&self) -> Option<u32> {
+        self.bus.traffic.and_then(|traffic| traffic.bytes_per_s())
+    }
+
+    /// Whether the traffic budget for the current second is used up
+    fn over_budget(&self) -> bool {
+        self.bus
+            .traffic
+            .is_some_and(|traffic| traffic.over_budget())
+    }
+
+    /// Account for a transfer of `bytes` on the bus
+    fn record_traffic(&mut self, bytes: usize) {
+        if self.bus.traffic.is_some() {
+            let now_us = self.now_us();
+            if let Some(traffic) = self.bus.traffic.as_mut() {
+                traffic.record(now_us, bytes);
+            }
+        }
+    }
+
+    /// Get the current time from the clock, if one is set
+    fn now_us(&mut self) -> Option<u64> {
+        self.bus.clock.as_mut().map(|clock| clock.now_us())
+    }
+
+    /// Get the format of the report packets, as set through the driver
+    pub fn packet_format(&self) -> PacketFormat {
+        PacketFormat::new(PosMode::MODE, self.shadows.intelli_mouse)
+    }
+
+    /// Read and decode the pending packet in the current packet format
+    fn read_packet(&mut self) -> Result<Packet, Error<E, PinError>> {
+        let format = self.packet_format();
+        let packet = match self.bus.combined_packet.take() {
+            Some(combined) => combined,
+            None => {
+                let mut packet = [0u8; 6];
+                match (format, self.over_budget()) {
+                    // The button byte is optional, the second byte is unused
+                    (PacketFormat::Absolute, true) => {
+                        self.read_regs(&Bank0::PACKET_BYTE2, &mut packet[2..])?
+                    }
+                    // So are the wheel counts
+                    (PacketFormat::IntelliMouse, true) => self.read_regs(
+                        &Bank0::PACKET_BYTE0,
+                        &mut packet[..PacketFormat::Relative.packet_len()],
+                    )?,
+                    _ => {
+                        self.read_regs(&Bank0::PACKET_BYTE0, &mut packet[..format.packet_len()])?
+                    }
+                }
+                packet
+            }
+        };
+        self.sync_frame();
+
+        Ok(format.decode(packet))
+    }
+
+    /// Notify the frame sync hook, if any, that a packet was read
+    fn sync_frame(&mut self) {
+        if self.hooks.frame_sync.is_some() {
+            let now_us = self.now_us();
+            if let Some(sync) = self.hooks.frame_sync.as_mut() {
+                sync.frame_read(now_us);
+            }
+        }
+    }
+
+    /// Account for a report being returned, returning its sequence number
+    fn record_report(&mut self) -> u32 {
+        #[cfg(feature = "metrics")]
+        {
+            let now_us = self.now_us();
+            self.metrics.record_report(now_us);
+        }
+
+        let sequence = self.pipeline.sequence;
+        self.pipeline.sequence = self.pipeline.sequence.wrapping_add(1);
+
+        sequence
+    }
+
+    /// Check the age of the pending report
+    /// Returns `None` if the report was stale and discarded.
+    fn check_report_age(&mut self) -> Result<Option<ReportAge>, Error<E, PinError>> {
+        let age_us = self
+            .now_us()
+            .zip(self.bus.last_clear_us)
+            .map(|(now, last_clear)| now.saturating_sub(last_clear).min(u32::MAX as u64) as u32);
+        let stale = matches!(
+            (age_us, self.bus.max_report_age_us),
+            (Some(age), Some(max_age)) if age > max_age
+        );
+
+        if stale && self.bus.stale_policy == StaleReportPolicy::Discard {
+            #[cfg(feature = "metrics")]
+            self.metrics.record_dropped_report();
+            self.finish_report()?;
+            return Ok(None);
+        }
+
+        Ok(Some(ReportAge { age_us, stale }))
+    }
+
+    /// Check if the touchpad has a report ready to be read
+    /// The hardware data ready line is checked first and, if it is asserted, confirmed with the
+    /// software data ready flag, as a (shared) line might be asserted by another device.
+    fn data_ready(&mut self) -> Result<bool, Error<E, PinError>> {
+        if !self.poll_calibration()? {
+            self.bus.report_confirmed = false;
+            return Ok(false);
+        }
+        // Confirmed by a budgeted poll that ran out of time, see `poll_budgeted()`
+        if core::mem::take(&mut self.bus.report_confirmed) {
+            return Ok(true);
+        }
+        if self.bus.flags_pending {
+            self.complete_report()?;
+        }
+        self.bus.combined_packet = None;
+        let hw_dr = match self.bus.dr_signal {
+            DataReadySignal::Edge if self.bus.dr_pending => Some(true),
+            _ => self.read_dr_line()?,
+        };
+        self.bus.dr_pending = false;
+        if hw_dr == Some(false) {
+            return Ok(false);
+        }
+        if let Some(sample_rate) = self
+            .power
+            .wake_boost
+            .filter(|_| hw_dr.is_some() && self.power.sleeping)
+        {
+            self.set_power_mode(PowerMode::Normal)?;
+            self.write_reg(&Bank0::SAMPLE_RATE, sample_rate)?;
+        }
+        if hw_dr.is_some() && self.over_budget() {
+            // Trust the line, confirming it is optional
+            return Ok(true);
+        }
+
+        if self.bus.combined_reads && !self.over_budget() {
+            let mut buffer = [0u8; COMBINED_READ_LEN];
+            self.read_regs(&Bank0::STATUS1, &mut buffer)?;
+            if buffer[0] & STATUS1_SW_DR == 0 {
+                return Ok(false);
+            }
+            let mut packet = [0u8; 6];
+            packet.copy_from_slice(&buffer[COMBINED_PACKET_OFFSET..]);
+            self.bus.combined_packet = Some(packet);
+            return Ok(true);
+        }
+
+        let sw_dr = self.read_reg(&Bank0::STATUS1)? & STATUS1_SW_DR;
+
+        Ok(sw_dr != 0)
+    }
+
+    /// Clear the flags after a report was read, unless clearing them is deferred
+    fn finish_report(&mut self) -> Result<(), Error<E, PinError>> {
+        if self.bus.lazy_clear {
+            self.bus.flags_pending = true;
+            return Ok(());
+        }
+
+        self.complete_report()
+    }
+
+    /// Clear the flags, re-checking the data ready line if configured
+    fn complete_report(&mut self) -> Result<(), Error<E, PinError>> {
+        self.clear_flags()?;
+
+        if self.bus.dr_recheck {
+            self.bus.dr_pending = self.read_dr_line()?.unwrap_or(false);
+        }
+
+        Ok(())
+    }
+
+    /// Read the hardware data ready line
+    /// Returns `None` if there is none or reading it failed and the pin error policy falls back to polling the
+    /// software data ready flag.
+    fn read_dr_line(&mut self) -> Result<Option<bool>, Error<E, PinError>> {
+        match self.bus.data_ready.is_high() {
+            None => Ok(None),
+            Some(Ok(level)) => {
+                if self.bus.dr_degraded {
+                    self.bus.dr_degraded = false;
+                    self.emit(Event::DataReadyRestored);
+                }
+                Ok(Some(level))
+            }
+            Some(Err(_)) if self.bus.pin_error_policy == PinErrorPolicy::PollSoftware => {
+                if !self.bus.dr_degraded {
+                    self.bus.dr_degraded = true;
+                    self.emit(Event::DataReadyDegraded);
+                }
+                Ok(None)
+            }
+            Some(Err(error)) => Err(error.into()),
+        }
+    }
+
+    /// Run a bus transaction transferring `bytes`, retrying it as often as the bus profile allows
+    fn with_retries<T>(
+        &mut self,
+        bytes: usize,
+        mut transaction: impl FnMut(&mut I2C, u8) -> Result<T, E>,
+    ) -> Result<T, Error<E, PinError>> {
+        let mut attempt = 0;
+        loop {
+            #[cfg(feature = "metrics")]
+            let start_us = self.now_us();
+            let result = transaction(&mut self.ll.i2c, self.ll.address.get());
+            self.record_traffic(bytes);
+            #[cfg(feature = "metrics")]
+            {
+                let duration_us = self
+                    .now_us()
+                    .zip(start_us)
+                    .map(|(end, start)| end.saturating_sub(start));
+                self.metrics.record_transaction(duration_us, result.is_ok());
+            }
+
+            let will_retry = result.is_err() && attempt < self.bus.bus_profile.retries();
+            let kind = result.as_ref().err().map(bus::error_kind::<I2C>);
+            self.bus.bus_health.record(kind, attempt, will_retry);
+
+            match result {
+                Ok(value) => return Ok(value),
+                Err(_) if will_retry => attempt += 1,
+                Err(error) => {
+                    return Err(Error::BusError {
+                        address: self.ll.address,
+                        error,
+                    })
+                }
+            }
+        }
     }
 
     /// Read the value of a register
     fn read_reg<R: Register>(&mut self, reg: &R) -> Result<u8, Error<E, PinError>> {
         let mut buffer = [0u8];
-
-        self.i2c
-            .write_read(
-                self.address as u8,
-                &[reg.addr() | Mask::Read as u8],
-                &mut buffer,
-            )
-            .map_err(|e| Error::BusError(e))?;
+        self.read_regs(reg, &mut buffer)?;
 
         Ok(buffer[0])
     }
 
+    /// Read consecutive registers, starting at `reg`
+    /// Registers are read in bursts of at most the bus profile's maximum burst length.
+    fn read_regs<R: Register>(
+        &mut self,
+        reg: &R,
+        buffer: &mut [u8],
+    ) -> Result<(), Error<E, PinError>> {
+        let burst_len = self.bus.bus_profile.max_burst_len();
+
+        for (i, chunk) in buffer.chunks_mut(burst_len).enumerate() {
+            let addr = reg.addr() + (i * burst_len) as u8;
+            self.with_retries(1 + chunk.len(), |i2c, address| {
+                i2c.write_read(address, &[addr | Mask::Read as u8], chunk)
+            })?;
+        }
+
+        Ok(())
+    }
+
     /// Write a value to a register
     fn write_reg<R: Register>(&mut self, reg: &R, value: u8) -> Result<(), Error<E, PinError>> {
         if reg.read_only() {
             Err(Error::SensorError(error::SensorError::WriteToReadOnly))
+        } else if self.bus.timings.post_write_us != 0 && self.bus.delay.is_none() {
+            // Checked up front, the write must not be issued without waiting after it
+            Err(Error::SensorError(error::SensorError::DelayRequired))
         } else {
-            self.i2c
-                .write(self.address as u8, &[reg.addr() | Mask::Write as u8, value])
-                .map_err(|e| Error::BusError(e))
+            self.with_retries(2, |i2c, address| {
+                i2c.write(address, &[reg.addr() | Mask::Write as u8, value])
+            })?;
+            self.settle();
+
+            Ok(())
+        }
+    }
+
+    /// Wait the post-write time of the timings
+    fn settle(&mut self) {
+        let wait_us = self.bus.timings.post_write_us;
+        if let Some(delay) = self.bus.delay.as_mut().filter(|_| wait_us != 0) {
+            delay.delay_us(wait_us);
         }
     }
 
+    /// Read the value of a bitfield
+    fn read_field<BF: Bitfield>(&mut self) -> Result<BF, Error<E, PinError>> {
+        let value = self.read_reg(&BF::REGISTER)?;
+        let field = BF::from_reg(value)?;
+
+        Ok(field)
+    }
+
     /// Update specific bits of a register
     fn update_reg<BF: Bitfield>(&mut self, value: BF) -> Result<(), Error<E, PinError>> {
         if BF::REGISTER.read_only() {
             Err(Error::SensorError(error::SensorError::WriteToReadOnly))
         } else {
             let current = self.read_reg(&BF::REGISTER)?;
-            let value = (current & !BF::BITMASK) | (value.bits() & BF::BITMASK);
-            self.write_reg(&BF::REGISTER, value)
+            let new = (current & !BF::BITMASK) | (value.bits() & BF::BITMASK);
+
+            self.shadows
+                .feed_config_shadow
+                .written(BF::REGISTER.addr(), new);
+            if !self.bus.bus_profile.verify_writes() {
+                return self.write_reg(&BF::REGISTER, new);
+            }
+
+            for _ in 0..=self.bus.bus_profile.retries() {
+                self.write_reg(&BF::REGISTER, new)?;
+                if self.read_reg(&BF::REGISTER)? & BF::BITMASK == new & BF::BITMASK {
+                    return Ok(());
+                }
+            }
+
+            Err(Error::SensorError(error::SensorError::WriteVerifyFailed))
         }
     }
 
     /// Clears the status flags.
-    /// This needs to be called after reading a position, otherwise no new position data is reported
-    fn clear_flags(&mut self) -> Result<(), Error<E, PinError>> {
-        self.write_reg(&Bank0::STATUS1, 0x00)
+    /// This needs to be called after reading a position, otherwise no new position data is reported.
+    /// The driver does this itself, unless clearing is deferred with `set_lazy_flag_clearing()`.
+    pub fn clear_flags(&mut self) -> Result<(), Error<E, PinError>> {
+        self.write_reg(&Bank0::STATUS1, 0x00)?;
+        self.bus.flags_pending = false;
+        self.bus.last_clear_us = self.now_us();
+
+        if self.bus.dr_handshake && self.read_dr_line()? == Some(true) {
+            // The line is rightly asserted again if the next report arrived in the meantime
+            let sw_dr = self.read_reg(&Bank0::STATUS1)? & STATUS1_SW_DR;
+            if sw_dr == 0 {
+                return Err(Error::HandshakeFailed);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, I2C, M: Module, PosMode, Feed, PinError> Pinnacle<'a, I2C, M, PosMode, Feed, PinError>
+where
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+{
+    /// Set the clock used for time-based features like report age tracking
+    pub fn set_clock(&mut self, clock: &'a mut dyn Clock) {
+        self.bus.clock = Some(clock);
+    }
+
+    /// Set the delay used for the post-write time of the timings, see `set_timings()`
+    pub fn set_delay(&mut self, delay: &'a mut dyn DelayNs) {
+        self.bus.delay = Some(delay);
+    }
+
+    /// Get the protocol timing
+    pub fn timings(&self) -> Timings {
+        self.bus.timings
+    }
+
+    /// Override the protocol timing, e.g. for clone modules needing longer settle times
+    /// A post-write time requires a delay to be set, see `set_delay()`.
+    pub fn set_timings(&mut self, timings: Timings) {
+        self.bus.timings = timings;
+    }
+
+    /// Get the touchpad variant, pinned by the module
+    pub fn variant(&self) -> Variant {
+        M::VARIANT
+    }
+
+    /// Absolute position of a report in micrometers from the center of the sensor area
+    /// Accounts for the axes being swapped by the mounting orientation and for the origin, but
+    /// not for transforms.
+    pub fn position_um(&self, data: &AbsoluteData) -> (i32, i32) {
+        let (x_pos, y_pos) =
+            self.pipeline
+                .origin
+                .revert(self.pipeline.orientation, data.x_pos, data.y_pos);
+        match self.pipeline.orientation.swapped() {
+            XYSwapped::Normal => M::VARIANT.position_um(x_pos, y_pos),
+            XYSwapped::Swapped => {
+                let (y, x) = M::VARIANT.position_um(y_pos, x_pos);
+                (x, y)
+            }
+        }
+    }
+
+    /// Velocity in micrometers per second between two absolute reports read `interval_us`
+    /// apart, e.g. using the clock
+    pub fn velocity_um_per_s(
+        &self,
+        from: &AbsoluteData,
+        to: &AbsoluteData,
+        interval_us: u32,
+    ) -> (i32, i32) {
+        let (x0, y0) = self.position_um(from);
+        let (x1, y1) = self.position_um(to);
+
+        (
+            variant::um_per_s(x1 - x0, interval_us),
+            variant::um_per_s(y1 - y0, interval_us),
+        )
+    }
+
+    /// Get the I²C address of the touchpad
+    pub fn address(&self) -> I2cAddress {
+        self.ll.address
+    }
+
+    /// Set a hook that is called whenever the speed preset changes, e.g. to give feedback
+    /// when presets are cycled
+    pub fn set_speed_hook(&mut self, hook: &'a mut dyn FnMut(SpeedPreset)) {
+        self.hooks.speed_hook = Some(hook);
+    }
+
+    /// Set the hooks invoked on taps, scroll detents and edge hits, e.g. for haptic feedback
+    pub fn set_haptic_hooks(&mut self, hooks: &'a mut dyn HapticHooks) {
+        self.hooks.haptics = Some(hooks);
+    }
+
+    /// Set the hooks invoked around power mode transitions, e.g. to gate external power rails
+    pub fn set_power_hooks(&mut self, hooks: &'a mut dyn PowerHooks) {
+        self.hooks.power_hooks = Some(hooks);
+    }
+
+    /// Set the origin of absolute positions, e.g. `CoordinateOrigin::TopLeft` for UI frameworks
+    /// The origin follows the mounting orientation. Processors like `GestureEngine` expect
+    /// native positions, feed them reports read with the native origin.
+    pub fn set_origin(&mut self, origin: CoordinateOrigin) {
+        self.pipeline.origin = origin;
+    }
+
+    /// Get the origin of absolute positions
+    pub fn origin(&self) -> CoordinateOrigin {
+        self.pipeline.origin
+    }
+
+    /// Set the transform applied to absolute positions after the mounting orientation and
+    /// the origin
+    /// Positions are clamped to `0..=u16::MAX` after the transform, and the offsets of
+    /// `CoordinateOrigin::Center` to the `i16` range.
+    pub fn set_transform(&mut self, transform: &'a dyn Transform) {
+        self.pipeline.transform = Some(transform);
+    }
+
+    /// Remove the transform applied to absolute positions
+    pub fn clear_transform(&mut self) {
+        self.pipeline.transform = None;
+    }
+
+    /// Report absolute positions also in polar coordinates around `center`, `None` to disable
+    /// The center is in the axes of the mounting orientation, e.g.
+    /// `PolarPosition::SENSOR_CENTER`. Transforms don't apply to polar coordinates.
+    pub fn set_polar_center(&mut self, center: Option<(u16, u16)>) {
+        self.pipeline.polar_center = center;
+    }
+
+    /// Smooth absolute positions while touching, weighting the previous position with
+    /// `weight`/256, 0 disables smoothing
+    /// Smoothing steadies noisy positions, e.g. with gloves, at the cost of lag. The Z level is
+    /// smoothed alike into `AbsoluteData::z_filtered`, `z_level` stays raw.
+    pub fn set_position_smoothing(&mut self, weight: u8) {
+        self.pipeline.position_smoothing = weight;
+    }
+
+    /// Enable/disable the automatic software smoothing when disabling the hardware filter, see
+    /// `set_filter_mode()`
+    pub fn set_auto_software_filter(&mut self, enabled: bool) {
+        self.pipeline.auto_software_filter = enabled;
+    }
+
+    /// Set the temperature source used by `compensate_temperature()`
+    pub fn set_temperature_source(&mut self, source: &'a mut dyn TemperatureSource) {
+        self.hooks.temperature_source = Some(source);
+    }
+
+    /// Set a sink mirroring every returned report and state change
+    pub fn set_event_sink(&mut self, sink: &'a mut dyn EventSink) {
+        self.hooks.event_sink = Some(sink);
+    }
+
+    /// Set regions where taps are ignored in relative mode, e.g. under a display bezel
+    /// Relative reports carry no position, so the position is estimated by adding up the
+    /// deltas, clamped to the usable area. The button bits of reports whose estimated position
+    /// falls into a region are cleared. Use `GestureEngine::set_tap_exclusions()` in absolute
+    /// mode.
+    pub fn set_tap_exclusions(&mut self, regions: &'a [Region]) {
+        self.pipeline.tap_exclusions = regions;
+        self.pipeline.position_estimate = None;
+    }
+
+    /// Set a hook called right after each packet was read, with the time it was read at
+    pub fn set_frame_sync(&mut self, sync: &'a mut dyn FrameSync) {
+        self.hooks.frame_sync = Some(sync);
+    }
+
+    /// Publish whether the touchpad is touched to `presence`, e.g. a `static` shared with
+    /// other parts of the firmware
+    pub fn set_touch_presence(&mut self, presence: &'a TouchPresence) {
+        self.hooks.touch_presence = Some(presence);
+    }
+
+    /// Pass an event to the event sink, if any
+    fn emit(&mut self, event: Event) {
+        if let Some(sink) = self.hooks.event_sink.as_mut() {
+            sink.event(&event);
+        }
+    }
+
+    /// Get the cursor speed preset applied to relative output
+    pub fn speed_preset(&self) -> SpeedPreset {
+        self.pipeline.speed.preset()
+    }
+
+    /// Set the cursor speed preset applied to relative output
+    pub fn set_speed_preset(&mut self, preset: SpeedPreset) {
+        self.pipeline.speed.set_preset(preset);
+        if let Some(hook) = self.hooks.speed_hook.as_mut() {
+            hook(preset);
+        }
+    }
+
+    /// Switch to the next speed preset, like a mouse DPI button
+    /// Bind this e.g. to secondary (corner) taps to let users cycle through presets.
+    pub fn cycle_speed_preset(&mut self) -> SpeedPreset {
+        let preset = self.pipeline.speed.preset().next();
+        self.set_speed_preset(preset);
+
+        preset
+    }
+
+    /// Get the scroll output configuration
+    pub fn scroll_config(&self) -> ScrollConfig {
+        self.pipeline.scroll.config()
+    }
+
+    /// Set the scroll output configuration
+    pub fn set_scroll_config(&mut self, config: ScrollConfig) {
+        self.pipeline.scroll.set_config(config);
+    }
+
+    /// Move the driver into a different typestate, keeping all other state
+    fn into_state<P: PositionReportingMode, F: FeedState>(
+        self,
+    ) -> Pinnacle<'a, I2C, M, P, F, PinError> {
+        Pinnacle {
+            ll: self.ll,
+            bus: self.bus,
+            pipeline: self.pipeline,
+            power: self.power,
+            shadows: self.shadows,
+            hooks: self.hooks,
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics,
+            _pos_state: PhantomData,
+            _feed_state: PhantomData,
+            _module: PhantomData,
+        }
     }
 }
-impl<'a, I2C, E, PinError> Tm040040<'a, I2C, Relative, NoFeed, PinError>
+impl<'a, I2C, E, M: Module> Pinnacle<'a, I2C, M, Relative, NoFeed, Infallible>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+{
+    /// Create a new trackpad instance for a data ready line handled elsewhere, e.g. when reads
+    /// are only triggered by its interrupt. Every read polls the software data ready flag.
+    /// There is no pin, so pin errors can't occur.
+    pub fn new_always_ready(i2c: I2C, address: impl Into<I2cAddress>) -> Self {
+        Self::with_data_ready_source(i2c, address, DataReadySource::AlwaysReady)
+    }
+}
+
+impl<'a, I2C, E, M: Module, PinError> Pinnacle<'a, I2C, M, Relative, NoFeed, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -273,20 +1601,81 @@ where
     //! Create a new trackpad instance.
     pub fn new(
         i2c: I2C,
-        address: Address,
+        address: impl Into<I2cAddress>,
         hardware_data_ready: &'a mut impl InputPin<Error = PinError>,
-    ) -> Tm040040<'a, I2C, Relative, NoFeed, PinError> {
-        Tm040040::<'a, I2C, Relative, NoFeed, PinError> {
-            i2c,
-            address,
-            hardware_data_ready,
+    ) -> Pinnacle<'a, I2C, M, Relative, NoFeed, PinError> {
+        Self::with_data_ready_source(i2c, address, DataReadySource::Pin(hardware_data_ready))
+    }
+
+    /// Create a new trackpad instance with the data ready line read from `source`, e.g. through
+    /// a GPIO expander, or without a data ready line at all
+    pub fn with_data_ready_source(
+        i2c: I2C,
+        address: impl Into<I2cAddress>,
+        data_ready: DataReadySource<'a, PinError>,
+    ) -> Pinnacle<'a, I2C, M, Relative, NoFeed, PinError> {
+        Pinnacle::<'a, I2C, M, Relative, NoFeed, PinError> {
+            ll: ll::Pinnacle::new(i2c, address),
+            bus: BusState::new(data_ready),
+            pipeline: Pipeline::default(),
+            power: PowerState::default(),
+            shadows: ConfigShadows::default(),
+            hooks: Hooks::default(),
+            #[cfg(feature = "metrics")]
+            metrics: Metrics::default(),
             _pos_state: PhantomData,
             _feed_state: PhantomData,
+            _module: PhantomData,
+        }
+    }
+
+    /// Create a new trackpad instance, verifying the chip's firmware ID
+    /// The variant can't be detected, it is pinned by the module `M`, see `Module`.
+    pub fn probe(
+        i2c: I2C,
+        address: impl Into<I2cAddress>,
+        hardware_data_ready: &'a mut impl InputPin<Error = PinError>,
+    ) -> Transition<'a, I2C, M, Relative, NoFeed, E, PinError> {
+        let mut trackpad = Self::new(i2c, address, hardware_data_ready);
+        if trackpad.device_id()? != PINNACLE_FIRMWARE_ID {
+            return Err(Error::SensorError(error::SensorError::BadChip));
+        }
+
+        Ok(trackpad)
+    }
+
+    /// Create a new trackpad instance like `probe()`, trying the primary, the secondary and
+    /// then the `custom` addresses until a touchpad responds, e.g. for products where the
+    /// address strap varies between hardware revisions.
+    /// The responding address is available through `address()`. If none responded, the error
+    /// of the last address is returned, or `SensorError::BadChip` if only other chips did.
+    pub fn probe_any(
+        i2c: I2C,
+        custom: &[I2cAddress],
+        hardware_data_ready: &'a mut impl InputPin<Error = PinError>,
+    ) -> Transition<'a, I2C, M, Relative, NoFeed, E, PinError> {
+        let mut trackpad = Self::new(i2c, Address::Primary, hardware_data_ready);
+        let mut bad_chip = false;
+        let mut last_error = None;
+        let addresses = [Address::Primary.into(), Address::Secondary.into()];
+        for &address in addresses.iter().chain(custom) {
+            trackpad.ll.address = address;
+            match trackpad.device_id() {
+                Ok(PINNACLE_FIRMWARE_ID) => return Ok(trackpad),
+                Ok(_) => bad_chip = true,
+                Err(error) => last_error = Some(error),
+            }
+        }
+
+        match last_error {
+            Some(error) if !bad_chip => Err(error),
+            _ => Err(Error::SensorError(error::SensorError::BadChip)),
         }
     }
 }
 
-impl<'a, I2C, E, PinError> Tm040040<'a, I2C, Relative, FeedEnabled, PinError>
+#[cfg(feature = "relative")]
+impl<'a, I2C, E, M: Module, PinError> Pinnacle<'a, I2C, M, Relative, FeedEnabled, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -294,40 +1683,147 @@ where
 {
     /// Read touchpad output as relative data (delta X and Y) plus button presses
     /// `None` if the touchpad isn't being touched.
+    /// With button decoupling enabled, this returns the data of `relative_event()`.
     pub fn relative_data(&mut self) -> Result<Option<RelativeData>, Error<E, PinError>> {
-        let hw_dr = self.hardware_data_ready.is_high()?;
-        if !hw_dr {
+        Ok(self.relative_event()?.map(RelativeEvent::into_data))
+    }
+
+    /// Read touchpad output as a relative event
+    /// `None` if the touchpad isn't being touched.
+    /// With button decoupling enabled, a report with both a button change and motion is split
+    /// into a `ButtonPress` event without motion, followed by a `Motion` event returned by the
+    /// next call. This keeps taps detected mid-motion from moving the cursor when forwarded to
+    /// a host. Without it, every report is returned as a `Motion` event.
+    pub fn relative_event(&mut self) -> Result<Option<RelativeEvent>, Error<E, PinError>> {
+        if let Some(mut data) = self
+            .pipeline
+            .button_decoupler
+            .as_mut()
+            .and_then(|d| d.pending())
+        {
+            data.sequence = self.record_report();
+            let event = RelativeEvent::Motion(data);
+            self.emit(Event::Relative(event));
+            return Ok(Some(event));
+        }
+        let Some(data) = self.read_relative()? else {
             return Ok(None);
+        };
+
+        let event = match self.pipeline.button_decoupler.as_mut() {
+            Some(decoupler) => decoupler.split(data),
+            None => RelativeEvent::Motion(data),
+        };
+        self.emit(Event::Relative(event));
+
+        Ok(Some(event))
+    }
+
+    /// Read up to `N` pending events at once
+    /// Reading stops at the first poll without new data, so handling a data ready interrupt
+    /// with this at high sample rates also picks up reports that arrived in the meantime.
+    pub fn poll<const N: usize>(&mut self) -> Result<Vec<RelativeEvent, N>, Error<E, PinError>> {
+        let mut events = Vec::new();
+        while !events.is_full() {
+            let Some(event) = self.relative_event()? else {
+                break;
+            };
+            // Can't fail, the vector isn't full
+            let _ = events.push(event);
         }
-        let sw_dr = self.read_reg(&Bank0::STATUS1)? & 0b0000_0100;
 
-        if sw_dr == 0 {
+        Ok(events)
+    }
+
+    /// Read and decode a relative report
+    fn read_relative(&mut self) -> Result<Option<RelativeData>, Error<E, PinError>> {
+        if !self.data_ready()? {
             return Ok(None);
         }
+        let Some(ReportAge { age_us, stale }) = self.check_report_age()? else {
+            return Ok(None);
+        };
 
-        let pb0 = self.read_reg(&Bank0::PACKET_BYTE0)?;
-        let pb1 = self.read_reg(&Bank0::PACKET_BYTE1)?;
-        let pb2 = self.read_reg(&Bank0::PACKET_BYTE2)?;
-
-        self.clear_flags()?;
+        let Packet::Relative(RelativePacket {
+            byte0,
+            primary_pressed,
+            secondary_pressed,
+            aux_pressed,
+            x_delta,
+            y_delta,
+            wheel,
+        }) = self.read_packet()?
+        else {
+            // Relative packet formats only decode to relative packets
+            return Ok(None);
+        };
 
-        let primary_pressed = (pb0 & 0x1) != 0;
-        let secondary_pressed = (pb0 & 0x2) != 0;
-        let aux_pressed = (pb0 & 0x4) != 0;
-        let x_sign = pb0 & 0b0001_0000;
-        let y_sign = pb0 & 0b0010_0000;
+        self.finish_report()?;
+        if self.discard_report() {
+            return Ok(None);
+        }
+        // Absolute packets don't have the bit set in the first byte
+        if !self.check_mode(byte0 & RELATIVE_BYTE0_ALWAYS_SET == 0)? {
+            return Ok(None);
+        }
+        self.audit_config_if_due()?;
 
-        let x_delta = if x_sign == 0 {
-            pb1 as i16
+        if let Some(validation) = self.pipeline.validation {
+            if !validation.relative_valid(byte0, x_delta, y_delta) {
+                return self.invalid_packet();
+            }
+        }
+        // Relative reports don't carry Z, motion or a held button is the best hint of a touch
+        if let Some(presence) = self.hooks.touch_presence {
+            presence.set(x_delta != 0 || y_delta != 0 || wheel != 0 || byte0 & 0b111 != 0);
+        }
+        let (primary_pressed, secondary_pressed, aux_pressed, byte0) =
+            if self.tap_excluded(x_delta, y_delta) {
+                (false, false, false, byte0 & !0b111)
+            } else {
+                (primary_pressed, secondary_pressed, aux_pressed, byte0)
+            };
+        let now_us = self.now_us();
+        let scrolling = self.pipeline.scroll.scrolling(wheel, now_us);
+        let scroll = self.pipeline.scroll.config();
+        let (primary_pressed, secondary_pressed, aux_pressed, byte0) =
+            if scrolling && scroll.suppress_taps {
+                // Only buttons held before the scroll started stay pressed
+                let held = self.pipeline.last_buttons;
+                (
+                    primary_pressed && held & 0b001 != 0,
+                    secondary_pressed && held & 0b010 != 0,
+                    aux_pressed && held & 0b100 != 0,
+                    byte0 & (!0b111 | held),
+                )
+            } else {
+                (primary_pressed, secondary_pressed, aux_pressed, byte0)
+            };
+        let (x_delta, y_delta) = if scrolling && scroll.suppress_motion {
+            (0, 0)
         } else {
-            (pb1 as i16) - 256
+            (x_delta, y_delta)
         };
-
-        let y_delta = if y_sign == 0 {
-            pb2 as i16
+        let (x_delta, y_delta) = self.pipeline.speed.scale(x_delta, y_delta);
+        // Scrolls lock on their raw counts, before the first detent
+        let x_delta = if scrolling && scroll.axis_lock {
+            0
         } else {
-            (pb2 as i16) - 256
+            x_delta
         };
+        let wheel = self.pipeline.scroll.wheel(wheel);
+
+        let buttons = byte0 & 0b111;
+        let pressed = buttons & !self.pipeline.last_buttons != 0;
+        self.pipeline.last_buttons = buttons;
+        if let Some(haptics) = self.hooks.haptics.as_mut() {
+            if pressed {
+                haptics.on_tap();
+            }
+            if wheel != 0 {
+                haptics.on_detent(wheel);
+            }
+        }
 
         Ok(Some(RelativeData {
             primary_pressed,
@@ -335,26 +1831,47 @@ where
             aux_pressed,
             x_delta,
             y_delta,
+            wheel,
+            axes: self.shadows.xy_enable,
+            age_us,
+            stale,
+            sequence: self.record_report(),
         }))
     }
 
+    /// Move the estimated position by a report's deltas, returning whether it falls into a tap
+    /// exclusion region
+    fn tap_excluded(&mut self, x_delta: i16, y_delta: i16) -> bool {
+        if self.pipeline.tap_exclusions.is_empty() {
+            return false;
+        }
+        let (x, y) = self.pipeline.position_estimate.unwrap_or((
+            (PINNACLE_X_LOWER + PINNACLE_X_UPPER) / 2,
+            (PINNACLE_Y_LOWER + PINNACLE_Y_UPPER) / 2,
+        ));
+        let x = (x as i32 + x_delta as i32).clamp(PINNACLE_X_LOWER as i32, PINNACLE_X_UPPER as i32);
+        let y = (y as i32 + y_delta as i32).clamp(PINNACLE_Y_LOWER as i32, PINNACLE_Y_UPPER as i32);
+        self.pipeline.position_estimate = Some((x as u16, y as u16));
+
+        self.pipeline
+            .tap_exclusions
+            .iter()
+            .any(|region| region.contains(x as u16, y as u16))
+    }
+
     /// Switch to absolute position mode
-    pub fn absolute(
-        mut self,
-    ) -> Result<Tm040040<'a, I2C, Absolute, FeedEnabled, PinError>, Error<E, PinError>> {
+    #[cfg(feature = "absolute")]
+    pub fn absolute(mut self) -> Transition<'a, I2C, M, Absolute, FeedEnabled, E, PinError> {
         self.set_position_mode(PositionMode::Absolute)?;
+        let mut pad: Pinnacle<'a, I2C, M, Absolute, FeedEnabled, PinError> = self.into_state();
+        pad.apply_orientation()?;
 
-        Ok(Tm040040 {
-            i2c: self.i2c,
-            address: self.address,
-            hardware_data_ready: self.hardware_data_ready,
-            _pos_state: PhantomData,
-            _feed_state: PhantomData,
-        })
+        Ok(pad)
     }
 }
 
-impl<I2C, E, Feed, PinError> Tm040040<'_, I2C, Relative, Feed, PinError>
+#[cfg(feature = "relative")]
+impl<I2C, E, M: Module, Feed, PinError> Pinnacle<'_, I2C, M, Relative, Feed, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -363,37 +1880,35 @@ where
 {
     /// Get axis swap state
     pub fn xy_swapped(&mut self) -> Result<XYSwapped, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & XYSwapped::BITMASK;
-        let mode = XYSwapped::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Swap X/Y axis
+    /// This changes the swapping part of the mounting orientation, see `set_orientation()`.
     pub fn set_xy_swapped(&mut self, yx: XYSwapped) -> Result<(), Error<E, PinError>> {
-        self.update_reg(yx)
+        self.set_orientation(MountingOrientation::from_flags(
+            yx,
+            self.pipeline.orientation.inverted(),
+        ))
     }
 
     /// Get Intelli mouse config
     pub fn intelli_mouse(&mut self) -> Result<IntelliMouseMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & IntelliMouseMode::BITMASK;
-        let mode = IntelliMouseMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set Intelli Mouse setting
     /// When enabled, reports back scroll position in relative mode (if supported)
     pub fn set_intelli_mouse(&mut self, im: IntelliMouseMode) -> Result<(), Error<E, PinError>> {
-        self.update_reg(im)
+        self.update_reg(im)?;
+        self.shadows.intelli_mouse = im;
+
+        Ok(())
     }
 
     /// Get tap detection mode
     pub fn tap_mode(&mut self) -> Result<TapMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & TapMode::BITMASK;
-        let mode = TapMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set tap detection mode
@@ -403,10 +1918,7 @@ where
 
     /// Get scroll mode
     pub fn scroll_mode(&mut self) -> Result<ScrollMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & ScrollMode::BITMASK;
-        let mode = ScrollMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Enable/disable scroll data
@@ -416,10 +1928,7 @@ where
 
     /// Get Glide extend config
     pub fn glide_extend_mode(&mut self) -> Result<GlideExtendMode, Error<E, PinError>> {
-        let bits = self.read_reg(&Bank0::FEED_CONFIG1)? & GlideExtendMode::BITMASK;
-        let mode = GlideExtendMode::try_from(bits)?;
-
-        Ok(mode)
+        self.read_field()
     }
 
     /// Set Glide extend config
@@ -432,7 +1941,8 @@ where
     }
 }
 
-impl<'a, I2C, E, PinError> Tm040040<'a, I2C, Absolute, FeedEnabled, PinError>
+#[cfg(feature = "absolute")]
+impl<'a, I2C, E, M: Module, PinError> Pinnacle<'a, I2C, M, Absolute, FeedEnabled, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -441,45 +1951,204 @@ where
     /// Read touchpad output (X/Y/Z position and button presses) in absolute mode
     /// Output is clipped to min/max usable position on the trackpad
     pub fn absolute_data(&mut self) -> Result<Option<AbsoluteData>, Error<E, PinError>> {
-        let hw_dr = self.hardware_data_ready.is_high()?;
-        if !hw_dr {
+        if !self.data_ready()? {
             return Ok(None);
         }
-        let button_state = self.read_reg(&Bank0::PACKET_BYTE0)? & 0x3F;
-        let x_low = self.read_reg(&Bank0::PACKET_BYTE2)?;
-        let y_low = self.read_reg(&Bank0::PACKET_BYTE3)?;
-        let x_y_high = self.read_reg(&Bank0::PACKET_BYTE4)?;
-        let z_level = self.read_reg(&Bank0::PACKET_BYTE5)? & 0x3F;
-        let x_pos = x_low as u16 | (((x_y_high & 0x0F) as u16) << 8);
-        let y_pos = y_low as u16 | (((x_y_high & 0xF0) as u16) << 4);
+        let Some(ReportAge { age_us, stale }) = self.check_report_age()? else {
+            return Ok(None);
+        };
+        let Packet::Absolute(AbsolutePacket {
+            button_state,
+            x_pos,
+            y_pos,
+            z_level,
+        }) = self.read_packet()?
+        else {
+            // The absolute packet format only decodes to absolute packets
+            return Ok(None);
+        };
 
-        self.clear_flags()?;
+        self.finish_report()?;
+        if self.discard_report() {
+            return Ok(None);
+        }
+        // Relative packets decode to positions outside the sensor range
+        if !self.check_mode(x_pos > PINNACLE_X_MAX || y_pos > PINNACLE_Y_MAX)? {
+            return Ok(None);
+        }
+        self.audit_config_if_due()?;
+
+        // Rejected packets are remembered too, so a genuine jump is only rejected once
+        let last = self.pipeline.last_absolute.replace((x_pos, y_pos, z_level));
+        if let Some(validation) = self.pipeline.validation {
+            if !validation.absolute_valid(x_pos, y_pos, z_level, last) {
+                return self.invalid_packet();
+            }
+        }
+        let multi_finger = self
+            .pipeline
+            .multi_finger
+            .as_mut()
+            .is_some_and(|detector| detector.update(x_pos, y_pos, z_level));
+
+        let jump = match last {
+            Some((last_x, last_y, last_z)) if last_z > 0 => {
+                x_pos.abs_diff(last_x).max(y_pos.abs_diff(last_y))
+            }
+            _ => 0,
+        };
+
+        let (x_pos, y_pos) = self.pipeline.orientation.apply_absolute(x_pos, y_pos);
+
+        let (raw_x, raw_y) = (x_pos, y_pos);
+        let (x_pos, y_pos, z_filtered) = self.smooth_position(x_pos, y_pos, z_level);
+        let residual = x_pos.abs_diff(raw_x).max(y_pos.abs_diff(raw_y));
+        let confidence = confidence::confidence(z_level, jump, residual, multi_finger);
+
+        let edge = if z_level > 0 {
+            self.pipeline.orientation.edge(x_pos, y_pos)
+        } else {
+            None
+        };
+        if let Some(edge) = edge.filter(|&edge| self.pipeline.last_edge != Some(edge)) {
+            if let Some(haptics) = self.hooks.haptics.as_mut() {
+                haptics.on_edge_hit(edge);
+            }
+            self.emit(Event::EdgeReached(edge));
+        }
+        self.pipeline.last_edge = edge;
+
+        let polar = self.pipeline.polar_center.map(|center| {
+            let swapped = self.pipeline.orientation.swapped() == XYSwapped::Swapped;
+            let (radius, angle) = polar::to_polar(x_pos, y_pos, center, swapped);
+            PolarPosition { radius, angle }
+        });
+
+        let (x_pos, y_pos, center_offset) = self.output_position(x_pos, y_pos);
+
+        if let Some(presence) = self.hooks.touch_presence {
+            presence.set(z_level > 0);
+        }
+
+        let data = AbsoluteData {
+            button_state,
+            x_pos,
+            y_pos,
+            z_level,
+            z_filtered,
+            confidence,
+            axes: self.shadows.xy_enable,
+            multi_finger,
+            polar,
+            center_offset,
+            age_us,
+            stale,
+            sequence: self.record_report(),
+        };
+        self.emit(Event::Absolute(data));
+
+        Ok(Some(data))
+    }
+
+    /// Decode the pending report without consuming it, e.g. for a diagnostic task observing
+    /// the stream while the main consumer reads and clears the reports.
+    /// The status flags are not cleared and no driver state is updated, so the position is
+    /// neither validated nor smoothed and `sequence` is the one the report will be returned
+    /// with. Returns `None` if no report is pending.
+    pub fn peek_absolute_data(&mut self) -> Result<Option<AbsoluteData>, Error<E, PinError>> {
+        if self.read_reg(&Bank0::STATUS1)? & STATUS1_SW_DR == 0 {
+            return Ok(None);
+        }
+        let mut packet = [0u8; 6];
+        self.read_regs(&Bank0::PACKET_BYTE0, &mut packet)?;
+        let AbsolutePacket {
+            button_state,
+            x_pos,
+            y_pos,
+            z_level,
+        } = AbsolutePacket::decode(packet);
+
+        let (x_pos, y_pos) = self.pipeline.orientation.apply_absolute(x_pos, y_pos);
+        let polar = self.pipeline.polar_center.map(|center| {
+            let swapped = self.pipeline.orientation.swapped() == XYSwapped::Swapped;
+            let (radius, angle) = polar::to_polar(x_pos, y_pos, center, swapped);
+            PolarPosition { radius, angle }
+        });
+        let (x_pos, y_pos, center_offset) = self.output_position(x_pos, y_pos);
 
         Ok(Some(AbsoluteData {
             button_state,
-            x_pos: x_pos.max(PINNACLE_X_UPPER).min(PINNACLE_X_LOWER),
-            y_pos: y_pos.max(PINNACLE_Y_UPPER).min(PINNACLE_Y_LOWER),
+            x_pos,
+            y_pos,
             z_level,
+            z_filtered: z_level,
+            confidence: confidence::confidence(z_level, 0, 0, false),
+            axes: self.shadows.xy_enable,
+            multi_finger: false,
+            polar,
+            center_offset,
+            age_us: None,
+            stale: false,
+            sequence: self.pipeline.sequence,
         }))
     }
 
+    /// Move an oriented position to the configured origin and apply the transform, returning
+    /// the position and, for `CoordinateOrigin::Center`, the signed offsets from the center
+    fn output_position(&self, x_pos: u16, y_pos: u16) -> (u16, u16, Option<(i16, i16)>) {
+        let transform = |(x, y)| match self.pipeline.transform {
+            Some(transform) => transform.apply(x, y),
+            None => (x, y),
+        };
+
+        let (x, y) = transform(
+            self.pipeline
+                .origin
+                .apply(self.pipeline.orientation, x_pos, y_pos),
+        );
+        let center_offset = (self.pipeline.origin == CoordinateOrigin::Center).then(|| {
+            let (x, y) = transform(self.pipeline.orientation.center_offset(x_pos, y_pos));
+            (
+                x.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+                y.clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+            )
+        });
+
+        (
+            x.clamp(0, u16::MAX as i32) as u16,
+            y.clamp(0, u16::MAX as i32) as u16,
+            center_offset,
+        )
+    }
+
+    /// Read up to `N` pending reports at once
+    /// Reading stops at the first poll without new data, so handling a data ready interrupt
+    /// with this at high sample rates also picks up reports that arrived in the meantime.
+    pub fn poll<const N: usize>(&mut self) -> Result<Vec<AbsoluteData, N>, Error<E, PinError>> {
+        let mut reports = Vec::new();
+        while !reports.is_full() {
+            let Some(report) = self.absolute_data()? else {
+                break;
+            };
+            // Can't fail, the vector isn't full
+            let _ = reports.push(report);
+        }
+
+        Ok(reports)
+    }
+
     /// Switch to relative position mode
-    pub fn relative(
-        mut self,
-    ) -> Result<Tm040040<'a, I2C, Relative, FeedEnabled, PinError>, Error<E, PinError>> {
+    #[cfg(feature = "relative")]
+    pub fn relative(mut self) -> Transition<'a, I2C, M, Relative, FeedEnabled, E, PinError> {
         self.set_position_mode(PositionMode::Relative)?;
+        let mut pad: Pinnacle<'a, I2C, M, Relative, FeedEnabled, PinError> = self.into_state();
+        pad.apply_orientation()?;
 
-        Ok(Tm040040 {
-            i2c: self.i2c,
-            address: self.address,
-            hardware_data_ready: self.hardware_data_ready,
-            _pos_state: PhantomData,
-            _feed_state: PhantomData,
-        })
+        Ok(pad)
     }
 }
 
-impl<'a, I2C, E, PosMode, PinError> Tm040040<'a, I2C, PosMode, FeedEnabled, PinError>
+impl<'a, I2C, E, M: Module, PosMode, PinError> Pinnacle<'a, I2C, M, PosMode, FeedEnabled, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -487,22 +2156,55 @@ where
     PinError: digital::Error,
 {
     /// Disable feed, no new data will be collected from sensor
-    pub fn disable(
-        mut self,
-    ) -> Result<Tm040040<'a, I2C, PosMode, NoFeed, PinError>, Error<E, PinError>> {
+    pub fn disable(mut self) -> Transition<'a, I2C, M, PosMode, NoFeed, E, PinError> {
         self.set_feed_mode(FeedMode::NoFeed)?;
 
-        Ok(Tm040040 {
-            i2c: self.i2c,
-            address: self.address,
-            hardware_data_ready: self.hardware_data_ready,
-            _pos_state: PhantomData,
-            _feed_state: PhantomData,
-        })
+        Ok(self.into_state())
+    }
+
+    /// Whether the touchpad is touched, checked without reading or clearing the pending report,
+    /// e.g. for screen wake or lock timers next to the main input loop.
+    ///
+    /// Only the data ready line is checked, or the software data ready flag if there is none or
+    /// it is handled elsewhere (`DataReadySource::AlwaysReady`).
+    /// With `check_z` the Z level of the pending packet is read too, so the Z-idle packets sent
+    /// after a lift in absolute mode don't count. Relative packets have no Z level, there it is
+    /// ignored. Always `false` while calibrating or suspended.
+    pub fn is_touched(&mut self, check_z: bool) -> Result<bool, Error<E, PinError>> {
+        if self.power.calibrating.is_some() || self.power.suspended.is_some() {
+            return Ok(false);
+        }
+        // The flags of a report that was already read would still be set
+        if self.bus.flags_pending {
+            self.complete_report()?;
+        }
+        let check_z = check_z && PosMode::MODE == PositionMode::Absolute;
+        if let Some(packet) = self.bus.combined_packet {
+            return Ok(!check_z || packet[5] & 0x3F != 0);
+        }
+
+        let pending = match self.bus.dr_signal {
+            DataReadySignal::Edge if self.bus.dr_pending => true,
+            signal => match self.read_dr_line()? {
+                Some(level) => {
+                    // Keep the edge for the next read of the report
+                    if signal == DataReadySignal::Edge {
+                        self.bus.dr_pending = level;
+                    }
+                    level
+                }
+                None => self.read_reg(&Bank0::STATUS1)? & STATUS1_SW_DR != 0,
+            },
+        };
+        if !pending || !check_z {
+            return Ok(pending);
+        }
+
+        Ok(self.read_reg(&Bank0::PACKET_BYTE5)? & 0x3F != 0)
     }
 }
 
-impl<'a, I2C, E, PosMode, PinError> Tm040040<'a, I2C, PosMode, NoFeed, PinError>
+impl<'a, I2C, E, M: Module, PosMode, PinError> Pinnacle<'a, I2C, M, PosMode, NoFeed, PinError>
 where
     I2C: I2c<Error = E>,
     E: Debug,
@@ -510,18 +2212,165 @@ where
     PinError: digital::Error,
 {
     /// enable feed, sensor starts collecting data
-    pub fn enable(
-        mut self,
-    ) -> Result<Tm040040<'a, I2C, PosMode, FeedEnabled, PinError>, Error<E, PinError>> {
+    pub fn enable(mut self) -> Transition<'a, I2C, M, PosMode, FeedEnabled, E, PinError> {
         self.set_feed_mode(FeedMode::Enabled)?;
         self.clear_flags()?;
+        self.pipeline.discard_remaining = self.pipeline.discard_reports;
 
-        Ok(Tm040040 {
-            i2c: self.i2c,
-            address: self.address,
-            hardware_data_ready: self.hardware_data_ready,
-            _pos_state: PhantomData,
-            _feed_state: PhantomData,
-        })
+        Ok(self.into_state())
+    }
+
+    /// Switch to absolute position mode and enable feed, so the first report is an absolute one
+    #[cfg(feature = "absolute")]
+    pub fn enable_absolute(self) -> Transition<'a, I2C, M, Absolute, FeedEnabled, E, PinError> {
+        self.enable_in_mode()
+    }
+
+    /// Switch to relative position mode and enable feed, so the first report is a relative one
+    #[cfg(feature = "relative")]
+    pub fn enable_relative(self) -> Transition<'a, I2C, M, Relative, FeedEnabled, E, PinError> {
+        self.enable_in_mode()
+    }
+
+    /// Set the position mode with feed disabled, drop anything reported in the old mode and
+    /// only then enable feed
+    fn enable_in_mode<P: PositionReportingMode>(
+        mut self,
+    ) -> Transition<'a, I2C, M, P, FeedEnabled, E, PinError> {
+        // The touchpad may still be reporting, e.g. after an external reset
+        self.set_feed_mode(FeedMode::NoFeed)?;
+        self.set_position_mode(P::MODE)?;
+        let mut pad: Pinnacle<'a, I2C, M, P, NoFeed, PinError> = self.into_state();
+        pad.apply_orientation()?;
+        pad.bus.combined_packet = None;
+        pad.clear_flags()?;
+
+        pad.enable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fake_bus::FakeBus,
+        register::{Bank0, Register},
+        Address, Tm040040, STATUS1_SW_DR,
+    };
+
+    /// Make `packet` the pending report of `bus`
+    fn report(bus: &mut FakeBus, packet: [u8; 6]) {
+        bus.regs[Bank0::PACKET_BYTE0.addr() as usize..][..6].copy_from_slice(&packet);
+        bus.regs[Bank0::STATUS1.addr() as usize] = STATUS1_SW_DR;
+    }
+
+    #[cfg(feature = "absolute")]
+    #[test]
+    fn clamps_absolute_positions_to_the_usable_area() {
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_absolute().unwrap();
+        // X 2000 beyond the upper bound, Y 10 below the lower bound, Z 20
+        report(&mut pad.ll.i2c, [0, 0, 0xd0, 0x0a, 0x07, 20]);
+
+        let data = pad.absolute_data().unwrap().unwrap();
+        assert_eq!(
+            (data.x_pos, data.y_pos),
+            (crate::PINNACLE_X_UPPER, crate::PINNACLE_Y_LOWER)
+        );
+    }
+
+    #[cfg(feature = "relative")]
+    #[test]
+    fn axis_lock_ends_with_a_scroll_short_of_a_detent() {
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_relative().unwrap();
+        pad.set_intelli_mouse(crate::IntelliMouseMode::Enabled)
+            .unwrap();
+        pad.set_scroll_config(crate::ScrollConfig {
+            counts_per_detent: 4,
+            axis_lock: true,
+            ..Default::default()
+        });
+
+        // One wheel count with X motion, then X motion only
+        report(&mut pad.ll.i2c, [0x08, 5, 0, 1, 0, 0]);
+        let data = pad.relative_data().unwrap().unwrap();
+        assert_eq!((data.x_delta, data.wheel), (0, 0));
+        report(&mut pad.ll.i2c, [0x08, 5, 0, 0, 0, 0]);
+        let data = pad.relative_data().unwrap().unwrap();
+        assert_eq!((data.x_delta, data.wheel), (5, 0));
+    }
+
+    #[cfg(feature = "relative")]
+    #[test]
+    fn always_ready_polls_the_software_data_ready_flag() {
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_relative().unwrap();
+
+        assert!(pad.relative_data().unwrap().is_none());
+        report(&mut pad.ll.i2c, [0x08, 5, 0, 0, 0, 0]);
+        assert!(pad.relative_data().unwrap().is_some());
+    }
+
+    #[cfg(feature = "absolute")]
+    #[test]
+    fn always_ready_is_touched_checks_the_software_data_ready_flag() {
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_absolute().unwrap();
+
+        assert!(!pad.is_touched(false).unwrap());
+        report(&mut pad.ll.i2c, [0, 0, 0, 0, 0, 20]);
+        assert!(pad.is_touched(true).unwrap());
+    }
+
+    #[cfg(feature = "relative")]
+    #[test]
+    fn post_write_time_without_delay_fails_before_writing() {
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_relative().unwrap();
+        pad.set_timings(crate::Timings {
+            post_write_us: 100,
+            ..Default::default()
+        });
+
+        assert!(matches!(
+            pad.set_intelli_mouse(crate::IntelliMouseMode::Enabled),
+            Err(crate::Error::SensorError(crate::SensorError::DelayRequired))
+        ));
+        assert_eq!(pad.ll.i2c.regs[Bank0::FEED_CONFIG2.addr() as usize], 0);
+    }
+
+    #[cfg(feature = "relative")]
+    #[test]
+    fn attach_reads_the_intelli_mouse_mode() {
+        let mut bus = FakeBus::new();
+        // Feed enabled in relative mode, IntelliMouse enabled
+        bus.regs[Bank0::FEED_CONFIG1.addr() as usize] = 0b01;
+        bus.regs[Bank0::FEED_CONFIG2.addr() as usize] = 0b01;
+        let mut data_ready = crate::fake_bus::FakePin(true);
+
+        let Ok(crate::Attached::RelativeFeed(mut pad)) =
+            Tm040040::attach(bus, Address::Primary, &mut data_ready)
+        else {
+            panic!("not attached in relative mode with feed enabled");
+        };
+        assert_eq!(pad.packet_format(), crate::PacketFormat::IntelliMouse);
+        report(&mut pad.ll.i2c, [0x08, 0, 0, 1, 0, 0]);
+        assert_eq!(pad.relative_data().unwrap().unwrap().wheel, 1);
+    }
+
+    #[cfg(feature = "relative")]
+    #[test]
+    fn relative_reports_update_touch_presence() {
+        let presence = crate::TouchPresence::new();
+        let pad = Tm040040::new_always_ready(FakeBus::new(), Address::Primary);
+        let mut pad = pad.enable_relative().unwrap();
+        pad.set_touch_presence(&presence);
+
+        report(&mut pad.ll.i2c, [0x08, 0, 3, 0, 0, 0]);
+        pad.relative_data().unwrap();
+        assert!(presence.is_touched());
+        report(&mut pad.ll.i2c, [0x08, 0, 0, 0, 0, 0]);
+        pad.relative_data().unwrap();
+        assert!(!presence.is_touched());
     }
 }
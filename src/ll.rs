@@ -0,0 +1,311 @@
+//! Low-level register interface.
+//!
+//! [`Pinnacle`] gives raw, typed access to the bank 0 registers of the Pinnacle ASIC, beneath
+//! the high-level typestate API. Each register has a proxy with `read()`, `write()` and
+//! `modify()`, and its fields are accessed through the config types, e.g.
+//!
+//! ```rust,ignore
+//! pinnacle.feed_config2().modify(|r, w| w.set(TapMode::AllTapsDisable))?;
+//! let power_mode: PowerMode = pinnacle.sys_config1().read()?.field()?;
+//! ```
+//!
+//! Transactions are neither retried nor verified, and the high-level driver doesn't notice
+//! changes made through this layer.
+
+use core::marker::PhantomData;
+
+use embedded_hal::i2c::I2c;
+
+use crate::{
+    config::{Bitfield, Mask},
+    error::SensorError,
+    FeedMode, FilterMode, GlideExtendMode, I2cAddress, IntelliMouseMode, PositionMode, PowerMode,
+    ScrollMode, TapMode, XYEnable, XYInverted, XYSwapped,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A register of the Pinnacle ASIC
+pub trait RegisterSpec: private::Sealed {
+    /// Address of the register in bank 0
+    const ADDR: u8;
+}
+
+/// A register that can be read
+pub trait Readable: RegisterSpec {}
+
+/// A register that can be written
+pub trait Writable: RegisterSpec {}
+
+/// A field of the register `REG`
+pub trait Field<REG: RegisterSpec>: Sized {
+    /// Bits of the register the field occupies
+    const MASK: u8;
+
+    /// Decode the field from a raw register value
+    fn from_bits(bits: u8) -> Result<Self, SensorError>;
+
+    /// Encode the field into its bits within the register
+    fn to_bits(self) -> u8;
+}
+
+/// Value read from the register `REG`
+#[derive(Clone, Copy, Debug)]
+pub struct R<REG> {
+    bits: u8,
+    _reg: PhantomData<REG>,
+}
+
+impl<REG: RegisterSpec> R<REG> {
+    pub(crate) fn new(bits: u8) -> Self {
+        Self {
+            bits,
+            _reg: PhantomData,
+        }
+    }
+
+    /// Raw value of the register
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Decode a field of the register
+    pub fn field<F: Field<REG>>(&self) -> Result<F, SensorError> {
+        F::from_bits(self.bits)
+    }
+}
+
+/// Value to be written to the register `REG`
+#[derive(Clone, Copy, Debug)]
+pub struct W<REG> {
+    bits: u8,
+    _reg: PhantomData<REG>,
+}
+
+impl<REG: RegisterSpec> W<REG> {
+    pub(crate) fn new(bits: u8) -> Self {
+        Self {
+            bits,
+            _reg: PhantomData,
+        }
+    }
+
+    pub(crate) fn value(&self) -> u8 {
+        self.bits
+    }
+
+    /// Set the raw value of the register
+    pub fn bits(&mut self, bits: u8) -> &mut Self {
+        self.bits = bits;
+        self
+    }
+
+    /// Set a field of the register, keeping all other bits
+    pub fn set<F: Field<REG>>(&mut self, field: F) -> &mut Self {
+        self.bits = (self.bits & !F::MASK) | (field.to_bits() & F::MASK);
+        self
+    }
+}
+
+/// Proxy for accessing the register `REG`
+pub struct Reg<'p, I2C, REG> {
+    pinnacle: &'p mut Pinnacle<I2C>,
+    _reg: PhantomData<REG>,
+}
+
+impl<I2C, E, REG> Reg<'_, I2C, REG>
+where
+    I2C: I2c<Error = E>,
+    REG: Readable,
+{
+    /// Read the register
+    pub fn read(&mut self) -> Result<R<REG>, E> {
+        let mut buffer = [0u8];
+        self.pinnacle.read_raw(REG::ADDR, &mut buffer)?;
+
+        Ok(R::new(buffer[0]))
+    }
+}
+
+impl<I2C, E, REG> Reg<'_, I2C, REG>
+where
+    I2C: I2c<Error = E>,
+    REG: Writable,
+{
+    /// Write the register, starting from all bits cleared
+    pub fn write(&mut self, f: impl FnOnce(&mut W<REG>) -> &mut W<REG>) -> Result<(), E> {
+        let mut w = W::new(0);
+        f(&mut w);
+
+        self.pinnacle.write_raw(REG::ADDR, w.value())
+    }
+}
+
+impl<I2C, E, REG> Reg<'_, I2C, REG>
+where
+    I2C: I2c<Error = E>,
+    REG: Readable + Writable,
+{
+    /// Read, modify and write back the register
+    pub fn modify(
+        &mut self,
+        f: impl for<'w> FnOnce(&R<REG>, &'w mut W<REG>) -> &'w mut W<REG>,
+    ) -> Result<(), E> {
+        let r = self.read()?;
+        let mut w = W::new(r.bits);
+        f(&r, &mut w);
+
+        self.pinnacle.write_raw(REG::ADDR, w.value())
+    }
+}
+
+/// Raw register access to a Pinnacle ASIC on an I²C bus
+pub struct Pinnacle<I2C> {
+    pub(crate) i2c: I2C,
+    pub(crate) address: I2cAddress,
+}
+
+impl<I2C, E> Pinnacle<I2C>
+where
+    I2C: I2c<Error = E>,
+{
+    /// Create a low-level interface to the touchpad at `address`
+    pub fn new(i2c: I2C, address: impl Into<I2cAddress>) -> Self {
+        Self {
+            i2c,
+            address: address.into(),
+        }
+    }
+
+    /// Return the underlying I2C instance for reuse
+    pub fn free(self) -> I2C {
+        self.i2c
+    }
+
+    /// Get the I²C address of the touchpad
+    pub fn address(&self) -> I2cAddress {
+        self.address
+    }
+
+    /// Read consecutive registers, starting at `addr`
+    pub fn read_raw(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), E> {
+        self.i2c
+            .write_read(self.address.get(), &[addr | Mask::Read as u8], buffer)
+    }
+
+    /// Write the register at `addr`
+    pub fn write_raw(&mut self, addr: u8, value: u8) -> Result<(), E> {
+        self.i2c
+            .write(self.address.get(), &[addr | Mask::Write as u8, value])
+    }
+}
+
+macro_rules! registers {
+    ($($(#[$doc:meta])* $name:ident: $accessor:ident = $addr:literal, $($access:ident)+;)*) => {
+        $(
+            $(#[$doc])*
+            #[derive(Clone, Copy, Debug)]
+            pub struct $name;
+            impl private::Sealed for $name {}
+            impl RegisterSpec for $name {
+                const ADDR: u8 = $addr;
+            }
+            $(impl $access for $name {})+
+        )*
+
+        impl<I2C> Pinnacle<I2C> {
+            $(
+                $(#[$doc])*
+                pub fn $accessor(&mut self) -> Reg<'_, I2C, $name> {
+                    Reg {
+                        pinnacle: self,
+                        _reg: PhantomData,
+                    }
+                }
+            )*
+        }
+    };
+}
+
+registers! {
+    /// Firmware ID
+    FirmwareId: firmware_id = 0x00, Readable;
+    /// Firmware version
+    FirmwareVersion: firmware_version = 0x01, Readable;
+    /// Status flags
+    Status1: status1 = 0x02, Readable Writable;
+    /// System configuration
+    SysConfig1: sys_config1 = 0x03, Readable Writable;
+    /// Feed configuration
+    FeedConfig1: feed_config1 = 0x04, Readable Writable;
+    /// Feed configuration
+    FeedConfig2: feed_config2 = 0x05, Readable Writable;
+    /// Calibration configuration
+    CalConfig1: cal_config1 = 0x07, Readable Writable;
+    /// PS/2 auxiliary control
+    Ps2AuxControl: ps2_aux_control = 0x08, Readable Writable;
+    /// Sample rate
+    SampleRate: sample_rate = 0x09, Readable Writable;
+    /// Number of Z-idle packets
+    ZIdle: z_idle = 0x0a, Readable Writable;
+    /// Z scaler
+    ZScaler: z_scaler = 0x0b, Readable Writable;
+    /// Sleep interval
+    SleepInterval: sleep_interval = 0x0c, Readable Writable;
+    /// Sleep timer
+    SleepTimer: sleep_timer = 0x0d, Readable Writable;
+    /// Packet byte 0
+    PacketByte0: packet_byte0 = 0x12, Readable;
+    /// Packet byte 1
+    PacketByte1: packet_byte1 = 0x13, Readable;
+    /// Packet byte 2
+    PacketByte2: packet_byte2 = 0x14, Readable;
+    /// Packet byte 3
+    PacketByte3: packet_byte3 = 0x15, Readable;
+    /// Packet byte 4
+    PacketByte4: packet_byte4 = 0x16, Readable;
+    /// Packet byte 5
+    PacketByte5: packet_byte5 = 0x17, Readable;
+    /// Extended register access value
+    EraValue: era_value = 0x1b, Readable Writable;
+    /// Extended register access address, high byte
+    EraHighByte: era_high_byte = 0x1c, Readable Writable;
+    /// Extended register access address, low byte
+    EraLowByte: era_low_byte = 0x1d, Readable Writable;
+    /// Extended register access control
+    EraControl: era_control = 0x1e, Readable Writable;
+}
+
+macro_rules! fields {
+    ($($field:ty => $reg:ty;)*) => {
+        $(
+            impl Field<$reg> for $field {
+                const MASK: u8 = <$field as Bitfield>::BITMASK;
+
+                fn from_bits(bits: u8) -> Result<Self, SensorError> {
+                    <$field as Bitfield>::from_reg(bits)
+                }
+
+                fn to_bits(self) -> u8 {
+                    <$field as Bitfield>::bits(self)
+                }
+            }
+        )*
+    };
+}
+
+fields! {
+    PowerMode => SysConfig1;
+    FeedMode => FeedConfig1;
+    PositionMode => FeedConfig1;
+    FilterMode => FeedConfig1;
+    XYEnable => FeedConfig1;
+    XYInverted => FeedConfig1;
+    IntelliMouseMode => FeedConfig2;
+    TapMode => FeedConfig2;
+    ScrollMode => FeedConfig2;
+    GlideExtendMode => FeedConfig2;
+    XYSwapped => FeedConfig2;
+}
@@ -0,0 +1,90 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{error::SensorError, Error, FeedState, Module, Pinnacle, PositionReportingMode};
+
+/// Extended register address of the compensation matrix
+const COMPENSATION_MATRIX_ADDR: u16 = 0x01DF;
+/// Length of the compensation matrix in bytes, 46 16-bit values
+pub const COMPENSATION_MATRIX_LEN: usize = 92;
+/// Length of `CalibrationData` as bytes
+pub const CALIBRATION_DATA_LEN: usize = COMPENSATION_MATRIX_LEN + 1;
+
+/// Calibration state of the touchpad, exported after a calibration and imported at boot, so
+/// devices with stable mechanical mounting can skip waiting for the power-on calibration, see
+/// `Tm040040::export_calibration()`.
+///
+/// The compensation matrix layout may differ between firmware versions, so the version it
+/// was exported from is kept and checked on import.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CalibrationData {
+    /// Firmware version of the chip the data was exported from
+    pub firmware_version: u8,
+    /// Raw compensation matrix
+    pub matrix: [u8; COMPENSATION_MATRIX_LEN],
+}
+
+impl CalibrationData {
+    /// Serialize the data for persistent storage: the firmware version, then the matrix
+    pub fn to_bytes(&self) -> [u8; CALIBRATION_DATA_LEN] {
+        let mut bytes = [0u8; CALIBRATION_DATA_LEN];
+        bytes[0] = self.firmware_version;
+        bytes[1..].copy_from_slice(&self.matrix);
+
+        bytes
+    }
+
+    /// Deserialize data serialized with `to_bytes()`
+    pub fn from_bytes(bytes: &[u8; CALIBRATION_DATA_LEN]) -> Self {
+        let mut matrix = [0u8; COMPENSATION_MATRIX_LEN];
+        matrix.copy_from_slice(&bytes[1..]);
+
+        Self {
+            firmware_version: bytes[0],
+            matrix,
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Export the calibration state, e.g. after `calibrate()` on a device with stable
+    /// mounting, to be imported with `import_calibration()` on later boots
+    pub fn export_calibration(&mut self) -> Result<CalibrationData, Error<E, PinError>> {
+        let firmware_version = self.firmware_version()?;
+        let mut matrix = [0u8; COMPENSATION_MATRIX_LEN];
+        self.read_era_burst(COMPENSATION_MATRIX_ADDR, &mut matrix)?;
+
+        Ok(CalibrationData {
+            firmware_version,
+            matrix,
+        })
+    }
+
+    /// Import a previously exported calibration state instead of waiting for a calibration
+    /// Fails with `SensorError::FirmwareMismatch` if the data was exported from a different
+    /// firmware version. The finger should not touch the pad until the import is done.
+    pub fn import_calibration(&mut self, data: &CalibrationData) -> Result<(), Error<E, PinError>> {
+        if self.firmware_version()? != data.firmware_version {
+            return Err(Error::SensorError(SensorError::FirmwareMismatch));
+        }
+
+        self.era_batch(|pad| {
+            for (offset, &value) in data.matrix.iter().enumerate() {
+                pad.write_era(COMPENSATION_MATRIX_ADDR + offset as u16, value)?;
+            }
+
+            Ok(())
+        })?;
+        self.pipeline.last_absolute = None;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+/// Length of the window reports per second are counted over
+const WINDOW_US: u64 = 1_000_000;
+
+/// Performance metrics collected by the driver, for tuning sample rates and diagnosing
+/// field performance issues.
+///
+/// Rates and timings require a clock to be set, see `Tm040040::set_clock()`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Metrics {
+    /// Number of reports returned
+    pub reports: u32,
+    /// Number of reports returned during the last full second
+    pub reports_per_sec: u32,
+    /// Number of reports read from the touchpad but dropped, e.g. because they were stale or
+    /// invalid
+    pub dropped_reports: u32,
+    /// Number of failed bus transactions, including ones that succeeded on retry
+    pub bus_errors: u32,
+    /// Number of timed bus transactions
+    pub transactions: u32,
+    /// Total time spent in timed bus transactions, in microseconds
+    pub transaction_time_us: u64,
+    window_start_us: Option<u64>,
+    window_reports: u32,
+}
+
+impl Metrics {
+    /// Average duration of a bus transaction in microseconds, `None` if none were timed
+    pub fn average_transaction_us(&self) -> Option<u64> {
+        self.transaction_time_us
+            .checked_div(self.transactions as u64)
+    }
+
+    pub(crate) fn record_report(&mut self, now_us: Option<u64>) {
+        self.reports = self.reports.wrapping_add(1);
+
+        let Some(now_us) = now_us else {
+            return;
+        };
+        let window_start_us = *self.window_start_us.get_or_insert(now_us);
+        if now_us.saturating_sub(window_start_us) >= WINDOW_US {
+            self.reports_per_sec = self.window_reports;
+            self.window_start_us = Some(now_us);
+            self.window_reports = 0;
+        }
+        self.window_reports += 1;
+    }
+
+    pub(crate) fn record_dropped_report(&mut self) {
+        self.dropped_reports = self.dropped_reports.wrapping_add(1);
+    }
+
+    pub(crate) fn record_transaction(&mut self, duration_us: Option<u64>, success: bool) {
+        if !success {
+            self.bus_errors = self.bus_errors.wrapping_add(1);
+        }
+        if let Some(duration_us) = duration_us {
+            self.transactions = self.transactions.wrapping_add(1);
+            self.transaction_time_us = self.transaction_time_us.saturating_add(duration_us);
+        }
+    }
+}
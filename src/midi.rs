@@ -0,0 +1,197 @@
+use heapless::Vec;
+
+use crate::{
+    AbsoluteData, Gesture, PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER,
+};
+
+/// Highest value of a MIDI data byte
+const MIDI_MAX: u8 = 127;
+
+/// A MIDI control change message
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CcMessage {
+    /// Channel, from 0 to 15
+    pub channel: u8,
+    /// Controller number, from 0 to 127
+    pub cc: u8,
+    /// Value, from 0 to 127
+    pub value: u8,
+}
+
+impl CcMessage {
+    /// The message as bytes on the wire
+    pub fn to_bytes(self) -> [u8; 3] {
+        [
+            0xB0 | (self.channel & 0x0F),
+            self.cc & MIDI_MAX,
+            self.value & MIDI_MAX,
+        ]
+    }
+}
+
+/// Part of an absolute report mapped to a controller
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CcSource {
+    /// X position, while touching
+    X,
+    /// Y position, while touching
+    Y,
+    /// Z level (pressure)
+    Z,
+}
+
+impl CcSource {
+    /// Default input range of the source
+    pub fn range(self) -> (u16, u16) {
+        match self {
+            Self::X => (PINNACLE_X_LOWER, PINNACLE_X_UPPER),
+            Self::Y => (PINNACLE_Y_LOWER, PINNACLE_Y_UPPER),
+            Self::Z => (0, 63),
+        }
+    }
+}
+
+/// Mapping of a report value to a controller
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CcMapping {
+    /// Mapped part of the report
+    pub source: CcSource,
+    /// Channel, from 0 to 15
+    pub channel: u8,
+    /// Controller number
+    pub cc: u8,
+    /// Input range mapped onto the output range, inputs outside are clamped
+    pub input: (u16, u16),
+    /// Output range, inverted if the first value is higher
+    pub output: (u8, u8),
+    /// Weight of the previous value in 1/256, 0 disables smoothing
+    pub smoothing: u8,
+}
+
+impl CcMapping {
+    /// Map the full range of `source` to the full controller range, without smoothing
+    pub fn new(source: CcSource, channel: u8, cc: u8) -> Self {
+        Self {
+            source,
+            channel,
+            cc,
+            input: source.range(),
+            output: (0, MIDI_MAX),
+            smoothing: 0,
+        }
+    }
+
+    /// Map an input value to the output range, in 1/256
+    fn map(&self, value: u16) -> u32 {
+        let (in_low, in_high) = (
+            self.input.0 as i32,
+            self.input.1.max(self.input.0.saturating_add(1)) as i32,
+        );
+        let (out_low, out_high) = (
+            self.output.0.min(MIDI_MAX) as i32,
+            self.output.1.min(MIDI_MAX) as i32,
+        );
+        let value = (value as i32).clamp(in_low, in_high);
+
+        (out_low * 256 + (value - in_low) * (out_high - out_low) * 256 / (in_high - in_low)) as u32
+    }
+}
+
+/// Controller value sent when a gesture is detected
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GestureCc {
+    /// Triggering gesture, taps trigger regardless of their location
+    pub gesture: Gesture,
+    /// Channel, from 0 to 15
+    pub channel: u8,
+    /// Controller number
+    pub cc: u8,
+    /// Value sent
+    pub value: u8,
+}
+
+/// Maps absolute reports and gestures to MIDI control changes, for DIY MIDI controllers.
+///
+/// Holds up to `N` report mappings and `N` gesture mappings. Messages are only produced when
+/// a controller value changes.
+#[derive(Clone, Debug)]
+pub struct MidiMapper<const N: usize> {
+    touch_z: u8,
+    mappings: Vec<(CcMapping, Option<u32>), N>,
+    gestures: Vec<GestureCc, N>,
+}
+
+impl<const N: usize> MidiMapper<N> {
+    /// New mapper, counting reports with a Z level of at least `touch_z` as touches
+    pub fn new(touch_z: u8) -> Self {
+        Self {
+            touch_z,
+            mappings: Vec::new(),
+            gestures: Vec::new(),
+        }
+    }
+
+    /// Add a report mapping, returning it back if `N` mappings are configured already
+    pub fn add(&mut self, mapping: CcMapping) -> Result<(), CcMapping> {
+        self.mappings
+            .push((mapping, None))
+            .map_err(|(mapping, _)| mapping)
+    }
+
+    /// Add a gesture mapping, returning it back if `N` mappings are configured already
+    pub fn add_gesture(&mut self, mapping: GestureCc) -> Result<(), GestureCc> {
+        self.gestures.push(mapping)
+    }
+
+    /// Process an absolute report, returning the messages for controllers that changed
+    pub fn update(&mut self, data: &AbsoluteData) -> Vec<CcMessage, N> {
+        let touching = data.z_level >= self.touch_z;
+        let mut messages = Vec::new();
+        for (mapping, state) in self.mappings.iter_mut() {
+            let input = match mapping.source {
+                CcSource::X if touching => data.x_pos,
+                CcSource::Y if touching => data.y_pos,
+                CcSource::Z => data.z_level as u16,
+                _ => continue,
+            };
+            let target = mapping.map(input);
+            let smoothed = match *state {
+                Some(previous) => {
+                    let weight = mapping.smoothing as u32;
+                    (previous * weight + target * (256 - weight)) / 256
+                }
+                None => target,
+            };
+            let previous_value = state.map(|previous| ((previous + 128) / 256) as u8);
+            *state = Some(smoothed);
+
+            let value = ((smoothed + 128) / 256) as u8;
+            if previous_value != Some(value) {
+                // Can't fail, there are at most `N` mappings
+                let _ = messages.push(CcMessage {
+                    channel: mapping.channel,
+                    cc: mapping.cc,
+                    value,
+                });
+            }
+        }
+
+        messages
+    }
+
+    /// Messages for a detected gesture, e.g. from a `GestureEngine`
+    pub fn gesture(&self, gesture: Gesture) -> Vec<CcMessage, N> {
+        self.gestures
+            .iter()
+            .filter(|mapping| match (mapping.gesture, gesture) {
+                (Gesture::Tap(_), Gesture::Tap(_)) => true,
+                (mapped, gesture) => mapped == gesture,
+            })
+            .map(|mapping| CcMessage {
+                channel: mapping.channel,
+                cc: mapping.cc,
+                value: mapping.value,
+            })
+            .collect()
+    }
+}
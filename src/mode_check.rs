@@ -0,0 +1,68 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{Error, FeedState, Module, Pinnacle, PositionReportingMode};
+
+/// Detection of the touchpad reporting in a different position mode than the driver expects,
+/// e.g. after an external reset, see `Tm040040::set_mode_check()`.
+///
+/// The position mode is read back every `interval` reports, and at once when a packet looks
+/// like it was encoded in the other mode.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModeCheck {
+    /// Number of reports between read-backs of the position mode, 0 to only read it back for
+    /// implausible packets
+    pub interval: u16,
+    /// Restore the expected position mode and drop the report instead of returning
+    /// `Error::ModeMismatch`
+    pub resync: bool,
+}
+
+impl Default for ModeCheck {
+    fn default() -> Self {
+        Self {
+            interval: 100,
+            resync: true,
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Check the position mode of the touchpad if due or if the report is `suspect`, returning
+    /// whether the report is valid
+    pub(crate) fn check_mode(&mut self, suspect: bool) -> Result<bool, Error<E, PinError>> {
+        let Some(check) = self.shadows.mode_check else {
+            return Ok(true);
+        };
+        self.shadows.reports_since_mode_check =
+            self.shadows.reports_since_mode_check.saturating_add(1);
+        let due = check.interval != 0 && self.shadows.reports_since_mode_check >= check.interval;
+        if !suspect && !due {
+            return Ok(true);
+        }
+        self.shadows.reports_since_mode_check = 0;
+
+        let found = self.position_mode()?;
+        if found == PosMode::MODE {
+            return Ok(true);
+        }
+        if !check.resync {
+            return Err(Error::ModeMismatch {
+                expected: PosMode::MODE,
+                found,
+            });
+        }
+        self.set_position_mode(PosMode::MODE)?;
+        self.apply_orientation()?;
+
+        Ok(false)
+    }
+}
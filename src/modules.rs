@@ -0,0 +1,27 @@
+//! Marker types of the supported touchpad modules, see `Module`.
+
+use crate::{Module, Variant};
+
+/// The 40mm TM040040 touchpad
+#[derive(Clone, Copy, Debug)]
+pub struct Tm040040;
+
+impl Module for Tm040040 {
+    const VARIANT: Variant = Variant::Tm040040;
+}
+
+/// The 35mm TM035035 touchpad
+#[derive(Clone, Copy, Debug)]
+pub struct Tm035035;
+
+impl Module for Tm035035 {
+    const VARIANT: Variant = Variant::Tm035035;
+}
+
+/// The 23mm TM023023 touchpad
+#[derive(Clone, Copy, Debug)]
+pub struct Tm023023;
+
+impl Module for Tm023023 {
+    const VARIANT: Variant = Variant::Tm023023;
+}
@@ -0,0 +1,79 @@
+/// Thresholds of the multi-finger heuristic.
+///
+/// The non-AG Pinnacle only tracks a single finger. When a second finger touches, the reported
+/// position jumps towards the center of both fingers while Z rises, and jumps back with Z
+/// falling when it is lifted again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiFingerConfig {
+    /// Minimum position change between two reports (on either axis) counted as a jump
+    pub min_jump: u16,
+    /// Minimum Z change accompanying a jump
+    pub min_z_change: u8,
+}
+
+impl Default for MultiFingerConfig {
+    fn default() -> Self {
+        Self {
+            min_jump: 200,
+            min_z_change: 6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Contact {
+    Single,
+    /// Probably more than one finger, with the Z level before the second finger touched
+    Multi {
+        single_z: u8,
+    },
+}
+
+/// Flags reports that probably stem from more than one finger touching the pad
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MultiFingerDetector {
+    config: MultiFingerConfig,
+    contact: Contact,
+    last: Option<(u16, u16, u8)>,
+}
+
+impl MultiFingerDetector {
+    pub fn new(config: MultiFingerConfig) -> Self {
+        Self {
+            config,
+            contact: Contact::Single,
+            last: None,
+        }
+    }
+
+    /// Process the next absolute report, returning whether it's probably a multi-finger report
+    pub fn update(&mut self, x: u16, y: u16, z: u8) -> bool {
+        let last = self.last.replace((x, y, z));
+        if z == 0 {
+            self.contact = Contact::Single;
+            return false;
+        }
+        let Some((last_x, last_y, last_z)) = last.filter(|&(_, _, last_z)| last_z != 0) else {
+            return false;
+        };
+
+        let jump = x.abs_diff(last_x).max(y.abs_diff(last_y)) >= self.config.min_jump;
+        match self.contact {
+            Contact::Single => {
+                if jump && z.saturating_sub(last_z) >= self.config.min_z_change {
+                    self.contact = Contact::Multi { single_z: last_z };
+                    return true;
+                }
+                false
+            }
+            Contact::Multi { single_z } => {
+                let lifted = jump && last_z.saturating_sub(z) >= self.config.min_z_change;
+                if lifted || z <= single_z.saturating_add(self.config.min_z_change / 2) {
+                    self.contact = Contact::Single;
+                }
+                // The frame the second finger is lifted in is still unreliable
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use core::{
+    convert::Infallible,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use embedded_hal::digital::{ErrorType, InputPin};
+
+/// Data ready notifications from a GPIO interrupt, e.g. an ESP-IDF `PinDriver` subscription,
+/// consumed by the driver as its data ready line.
+///
+/// The interrupt handler calls `notify()` on the rising edge of the data ready line, and the
+/// driver reads the notification through a `NotifiedPin`. Reading consumes the notification,
+/// so configure `DataReadySignal::Edge`. Only loads and stores are used, so this works on
+/// targets without compare-and-swap.
+///
+/// ```rust,ignore
+/// static DATA_READY: DataReadyNotifier = DataReadyNotifier::new();
+///
+/// let mut pin = PinDriver::input(peripherals.pins.gpio4)?;
+/// pin.set_interrupt_type(InterruptType::PosEdge)?;
+/// let notification = Notification::new();
+/// let notifier = notification.notifier();
+/// unsafe {
+///     pin.subscribe(move || {
+///         DATA_READY.notify();
+///         notifier.notify_and_yield(NonZeroU32::new(1).unwrap());
+///     })?;
+/// }
+///
+/// let mut data_ready = DATA_READY.pin();
+/// let mut trackpad = Tm040040::new(i2c, Address::Primary, &mut data_ready).absolute()?;
+/// trackpad.set_data_ready_signal(DataReadySignal::Edge);
+/// loop {
+///     pin.enable_interrupt()?;
+///     notification.wait(BLOCK);
+///     while let Some(data) = trackpad.absolute_data()? {
+///         // Handle the report
+///     }
+/// }
+/// ```
+pub struct DataReadyNotifier {
+    pending: AtomicBool,
+}
+
+impl DataReadyNotifier {
+    /// Create a notifier without a pending notification
+    pub const fn new() -> Self {
+        Self {
+            pending: AtomicBool::new(false),
+        }
+    }
+
+    /// Signal a pending report, safe to call from an interrupt handler
+    pub fn notify(&self) {
+        self.pending.store(true, Ordering::Release);
+    }
+
+    /// Consume the pending notification, if any
+    pub fn take(&self) -> bool {
+        if !self.pending.load(Ordering::Acquire) {
+            return false;
+        }
+        // A notification arriving in between belongs to the same report, the line is only
+        // asserted again once the driver cleared the flags
+        self.pending.store(false, Ordering::Relaxed);
+
+        true
+    }
+
+    /// Get a pin reading this notifier, to be passed to the driver
+    pub fn pin(&self) -> NotifiedPin<'_> {
+        NotifiedPin { notifier: self }
+    }
+}
+
+impl Default for DataReadyNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The data ready line as seen through a `DataReadyNotifier`, high once per notification
+pub struct NotifiedPin<'a> {
+    notifier: &'a DataReadyNotifier,
+}
+
+impl ErrorType for NotifiedPin<'_> {
+    type Error = Infallible;
+}
+
+impl InputPin for NotifiedPin<'_> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.notifier.take())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|high| !high)
+    }
+}
@@ -0,0 +1,191 @@
+use crate::{
+    Edge, XYInverted, XYSwapped, PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER,
+    PINNACLE_Y_UPPER,
+};
+
+/// Physical mounting orientation of the touchpad, i.e. the transformation needed to get
+/// the reported axes to line up with the device axes.
+///
+/// Every orientation is a combination of swapping X and Y and then inverting the resulting
+/// axes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum MountingOrientation {
+    /// Axes are used as reported
+    #[default]
+    Normal,
+    /// Rotated by 90°: X and Y swapped, then Y inverted
+    Rotate90,
+    /// Rotated by 180°: X and Y inverted
+    Rotate180,
+    /// Rotated by 270°: X and Y swapped, then X inverted
+    Rotate270,
+    /// Mirrored horizontally: X inverted
+    MirrorX,
+    /// Mirrored vertically: Y inverted
+    MirrorY,
+    /// Mirrored along the diagonal: X and Y swapped
+    Transpose,
+    /// Mirrored along the anti-diagonal: X and Y swapped, then X and Y inverted
+    AntiTranspose,
+}
+
+impl MountingOrientation {
+    /// All orientations, in declaration order
+    pub const ALL: [Self; 8] = [
+        Self::Normal,
+        Self::Rotate90,
+        Self::Rotate180,
+        Self::Rotate270,
+        Self::MirrorX,
+        Self::MirrorY,
+        Self::Transpose,
+        Self::AntiTranspose,
+    ];
+
+    /// Whether X and Y need to be swapped for this orientation
+    pub fn swapped(self) -> XYSwapped {
+        match self {
+            Self::Rotate90 | Self::Rotate270 | Self::Transpose | Self::AntiTranspose => {
+                XYSwapped::Swapped
+            }
+            _ => XYSwapped::Normal,
+        }
+    }
+
+    /// Which axes need to be inverted (after swapping) for this orientation
+    pub fn inverted(self) -> XYInverted {
+        match self {
+            Self::Normal | Self::Transpose => XYInverted::Normal,
+            Self::Rotate270 | Self::MirrorX => XYInverted::XInverted,
+            Self::Rotate90 | Self::MirrorY => XYInverted::YInverted,
+            Self::Rotate180 | Self::AntiTranspose => XYInverted::XYInverted,
+        }
+    }
+
+    /// Get the orientation resulting from swapping and then inverting axes
+    pub fn from_flags(swapped: XYSwapped, inverted: XYInverted) -> Self {
+        match (swapped, inverted) {
+            (XYSwapped::Normal, XYInverted::Normal) => Self::Normal,
+            (XYSwapped::Normal, XYInverted::XInverted) => Self::MirrorX,
+            (XYSwapped::Normal, XYInverted::YInverted) => Self::MirrorY,
+            (XYSwapped::Normal, XYInverted::XYInverted) => Self::Rotate180,
+            (XYSwapped::Swapped, XYInverted::Normal) => Self::Transpose,
+            (XYSwapped::Swapped, XYInverted::XInverted) => Self::Rotate270,
+            (XYSwapped::Swapped, XYInverted::YInverted) => Self::Rotate90,
+            (XYSwapped::Swapped, XYInverted::XYInverted) => Self::AntiTranspose,
+        }
+    }
+
+    /// Clamp an absolute position to the usable area of the touchpad and apply the orientation
+    /// When swapped, the X output covers the Y range and vice versa.
+    pub(crate) fn apply_absolute(self, x: u16, y: u16) -> (u16, u16) {
+        let x_range = (PINNACLE_X_LOWER, PINNACLE_X_UPPER);
+        let y_range = (PINNACLE_Y_LOWER, PINNACLE_Y_UPPER);
+        let x = x.clamp(x_range.0, x_range.1);
+        let y = y.clamp(y_range.0, y_range.1);
+        let (x, y, x_range, y_range) = match self.swapped() {
+            XYSwapped::Normal => (x, y, x_range, y_range),
+            XYSwapped::Swapped => (y, x, y_range, x_range),
+        };
+        let inverted = self.inverted();
+        let x = if matches!(inverted, XYInverted::XInverted | XYInverted::XYInverted) {
+            x_range.0 + x_range.1 - x
+        } else {
+            x
+        };
+        let y = if matches!(inverted, XYInverted::YInverted | XYInverted::XYInverted) {
+            y_range.0 + y_range.1 - y
+        } else {
+            y
+        };
+
+        (x, y)
+    }
+
+    /// Usable X and Y ranges of oriented absolute positions
+    fn ranges(self) -> ((u16, u16), (u16, u16)) {
+        let x_range = (PINNACLE_X_LOWER, PINNACLE_X_UPPER);
+        let y_range = (PINNACLE_Y_LOWER, PINNACLE_Y_UPPER);
+        match self.swapped() {
+            XYSwapped::Normal => (x_range, y_range),
+            XYSwapped::Swapped => (y_range, x_range),
+        }
+    }
+
+    /// Signed offsets of an oriented absolute position from the center of the usable area
+    pub(crate) fn center_offset(self, x: u16, y: u16) -> (i32, i32) {
+        let ((x_low, x_high), (y_low, y_high)) = self.ranges();
+
+        (
+            x as i32 - (x_low + x_high) as i32 / 2,
+            y as i32 - (y_low + y_high) as i32 / 2,
+        )
+    }
+
+    /// The edge an oriented absolute position lies on, if any
+    pub(crate) fn edge(self, x: u16, y: u16) -> Option<Edge> {
+        let (x_range, y_range) = self.ranges();
+
+        if x <= x_range.0 {
+            Some(Edge::Left)
+        } else if x >= x_range.1 {
+            Some(Edge::Right)
+        } else if y <= y_range.0 {
+            Some(Edge::Top)
+        } else if y >= y_range.1 {
+            Some(Edge::Bottom)
+        } else {
+            None
+        }
+    }
+}
+
+/// Origin of reported absolute positions, see `Tm040040::set_origin()`
+///
+/// The origin refers to the usable area in the axes of the mounting orientation, so e.g.
+/// `TopLeft` is the top left corner as the device is held.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum CoordinateOrigin {
+    /// Positions as reported by the sensor, with the usable area starting at 128/64
+    #[default]
+    Native,
+    /// Top left corner of the usable area, Y increasing downwards
+    TopLeft,
+    /// Bottom left corner of the usable area, Y increasing upwards
+    BottomLeft,
+    /// Center of the usable area, Y increasing downwards, e.g. for joystick-like control
+    /// loops. The signed offsets from the center are reported in `AbsoluteData::center_offset`,
+    /// also read by converting the report into `(i16, i16)`, while `x_pos`/`y_pos` stay
+    /// unsigned positions from the top left corner like `TopLeft`.
+    Center,
+}
+
+/// A report without signed offsets was converted into `(i16, i16)`, its origin is not
+/// `CoordinateOrigin::Center`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NotCentered;
+
+impl CoordinateOrigin {
+    /// Move an oriented absolute position to this origin
+    pub(crate) fn apply(self, orientation: MountingOrientation, x: u16, y: u16) -> (i32, i32) {
+        let ((x_low, _), (y_low, y_high)) = orientation.ranges();
+        let (x, y) = (x as i32, y as i32);
+
+        match self {
+            Self::Native => (x, y),
+            Self::TopLeft | Self::Center => (x - x_low as i32, y - y_low as i32),
+            Self::BottomLeft => (x - x_low as i32, y_high as i32 - y),
+        }
+    }
+
+    /// Move a position at this origin back to the native origin
+    pub(crate) fn revert(self, orientation: MountingOrientation, x: u16, y: u16) -> (u16, u16) {
+        let ((x_low, _), (y_low, y_high)) = orientation.ranges();
+
+        match self {
+            Self::Native => (x, y),
+            Self::TopLeft | Self::Center => (x.wrapping_add(x_low), y.wrapping_add(y_low)),
+            Self::BottomLeft => (x.wrapping_add(x_low), y_high.wrapping_sub(y)),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Decoding of the report packets, separate from bus access so it can be checked against the
+//! packet fixtures.
+
+#[cfg(test)]
+mod fixtures;
+
+use crate::{IntelliMouseMode, PositionMode};
+
+/// Layout of the report packets, depending on the position mode and IntelliMouse mode, see
+/// `Tm040040::packet_format()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PacketFormat {
+    /// Relative mode, 3 bytes: byte 0 the buttons (bits 0-2), an always set bit (bit 3) and the
+    /// sign bits of the X and Y deltas (bits 4, 5), bytes 1 and 2 the low bytes of the X and Y
+    /// deltas
+    Relative,
+    /// Relative mode with IntelliMouse enabled, 4 bytes: like `Relative`, byte 3 the wheel
+    /// counts
+    IntelliMouse,
+    /// Absolute mode, 6 bytes: byte 0 the buttons (bits 0-5), byte 1 unused, bytes 2 and 3 the
+    /// low bytes of the X and Y position, byte 4 their high nibbles (X in bits 0-3, Y in bits
+    /// 4-7), byte 5 the Z level (bits 0-5)
+    Absolute,
+}
+
+impl PacketFormat {
+    /// The format reported with the given configuration
+    pub fn new(position_mode: PositionMode, intelli_mouse: IntelliMouseMode) -> Self {
+        match (position_mode, intelli_mouse) {
+            (PositionMode::Absolute, _) => Self::Absolute,
+            (PositionMode::Relative, IntelliMouseMode::Disabled) => Self::Relative,
+            (PositionMode::Relative, IntelliMouseMode::Enabled) => Self::IntelliMouse,
+        }
+    }
+
+    /// Length of a packet in bytes
+    pub const fn packet_len(self) -> usize {
+        match self {
+            Self::Relative => 3,
+            Self::IntelliMouse => 4,
+            Self::Absolute => 6,
+        }
+    }
+
+    /// Decode a packet, bytes beyond the length of the format are ignored
+    pub(crate) fn decode(self, packet: [u8; 6]) -> Packet {
+        match self {
+            Self::Relative => {
+                Packet::Relative(RelativePacket::decode([packet[0], packet[1], packet[2], 0]))
+            }
+            Self::IntelliMouse => Packet::Relative(RelativePacket::decode([
+                packet[0], packet[1], packet[2], packet[3],
+            ])),
+            Self::Absolute => Packet::Absolute(AbsolutePacket::decode(packet)),
+        }
+    }
+}
+
+/// A decoded packet of any format
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Packet {
+    Relative(RelativePacket),
+    Absolute(AbsolutePacket),
+}
+
+/// A decoded relative mode packet
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct RelativePacket {
+    /// Raw first byte, carrying buttons, sign bits and the always set bit
+    pub byte0: u8,
+    pub primary_pressed: bool,
+    pub secondary_pressed: bool,
+    pub aux_pressed: bool,
+    pub x_delta: i16,
+    pub y_delta: i16,
+    /// Wheel counts in IntelliMouse mode, 0 otherwise
+    pub wheel: i8,
+}
+
+impl RelativePacket {
+    pub fn decode(packet: [u8; 4]) -> Self {
+        let [pb0, pb1, pb2, pb3] = packet;
+        let x_sign = pb0 & 0b0001_0000;
+        let y_sign = pb0 & 0b0010_0000;
+
+        let x_delta = if x_sign == 0 {
+            pb1 as i16
+        } else {
+            (pb1 as i16) - 256
+        };
+
+        let y_delta = if y_sign == 0 {
+            pb2 as i16
+        } else {
+            (pb2 as i16) - 256
+        };
+
+        Self {
+            byte0: pb0,
+            primary_pressed: (pb0 & 0x1) != 0,
+            secondary_pressed: (pb0 & 0x2) != 0,
+            aux_pressed: (pb0 & 0x4) != 0,
+            x_delta,
+            y_delta,
+            wheel: pb3 as i8,
+        }
+    }
+}
+
+/// A decoded absolute mode packet, with the raw (unclamped) position
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct AbsolutePacket {
+    pub button_state: u8,
+    pub x_pos: u16,
+    pub y_pos: u16,
+    pub z_level: u8,
+}
+
+impl AbsolutePacket {
+    pub fn decode(packet: [u8; 6]) -> Self {
+        let x_low = packet[2];
+        let y_low = packet[3];
+        let x_y_high = packet[4];
+
+        Self {
+            button_state: packet[0] & 0x3F,
+            x_pos: x_low as u16 | (((x_y_high & 0x0F) as u16) << 8),
+            y_pos: y_low as u16 | (((x_y_high & 0xF0) as u16) << 4),
+            z_level: packet[5] & 0x3F,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        fixtures::{ABSOLUTE, RELATIVE},
+        AbsolutePacket, Packet, PacketFormat, RelativePacket,
+    };
+
+    #[test]
+    fn decodes_relative_fixtures() {
+        for fixture in RELATIVE {
+            assert_eq!(
+                RelativePacket::decode(fixture.bytes),
+                fixture.expected,
+                "{}",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn decodes_absolute_fixtures() {
+        for fixture in ABSOLUTE {
+            assert_eq!(
+                AbsolutePacket::decode(fixture.bytes),
+                fixture.expected,
+                "{}",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn relative_format_ignores_wheel_byte() {
+        for fixture in RELATIVE {
+            let mut bytes = [0u8; 6];
+            bytes[..4].copy_from_slice(&fixture.bytes);
+            let expected = RelativePacket {
+                wheel: 0,
+                ..fixture.expected
+            };
+            assert_eq!(
+                PacketFormat::Relative.decode(bytes),
+                Packet::Relative(expected),
+                "{}",
+                fixture.name
+            );
+        }
+    }
+
+    #[test]
+    fn relative_fixtures_have_always_set_bit() {
+        for fixture in RELATIVE {
+            assert_ne!(
+                fixture.bytes[0] & crate::RELATIVE_BYTE0_ALWAYS_SET,
+                0,
+                "{}",
+                fixture.name
+            );
+        }
+    }
+}
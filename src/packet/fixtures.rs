@@ -0,0 +1,146 @@
+//! Packet fixtures for the decoder tests.
+//!
+//! The packets are laid out byte-for-byte after the packet formats of the Pinnacle ASIC
+//! documentation (relative with IntelliMouse wheel byte, absolute with Z-idle packets). They are
+//! constructed rather than captured from a device: when decoding behaves differently on real
+//! hardware, add the captured packet here.
+
+use super::{AbsolutePacket, RelativePacket};
+
+pub(super) struct Fixture<const LEN: usize, T> {
+    pub name: &'static str,
+    pub bytes: [u8; LEN],
+    pub expected: T,
+}
+
+const fn relative(byte0: u8, x_delta: i16, y_delta: i16, wheel: i8) -> RelativePacket {
+    RelativePacket {
+        byte0,
+        primary_pressed: byte0 & 0x1 != 0,
+        secondary_pressed: byte0 & 0x2 != 0,
+        aux_pressed: byte0 & 0x4 != 0,
+        x_delta,
+        y_delta,
+        wheel,
+    }
+}
+
+const fn absolute(button_state: u8, x_pos: u16, y_pos: u16, z_level: u8) -> AbsolutePacket {
+    AbsolutePacket {
+        button_state,
+        x_pos,
+        y_pos,
+        z_level,
+    }
+}
+
+pub(super) const RELATIVE: &[Fixture<4, RelativePacket>] = &[
+    Fixture {
+        name: "relative: no motion",
+        bytes: [0x08, 0x00, 0x00, 0x00],
+        expected: relative(0x08, 0, 0, 0),
+    },
+    Fixture {
+        name: "relative: right",
+        bytes: [0x08, 0x05, 0x00, 0x00],
+        expected: relative(0x08, 5, 0, 0),
+    },
+    Fixture {
+        name: "relative: left",
+        bytes: [0x18, 0xFB, 0x00, 0x00],
+        expected: relative(0x18, -5, 0, 0),
+    },
+    Fixture {
+        name: "relative: up",
+        bytes: [0x28, 0x00, 0xF0, 0x00],
+        expected: relative(0x28, 0, -16, 0),
+    },
+    Fixture {
+        name: "relative: largest positive deltas",
+        bytes: [0x08, 0xFF, 0xFF, 0x00],
+        expected: relative(0x08, 255, 255, 0),
+    },
+    Fixture {
+        name: "relative: largest negative deltas",
+        bytes: [0x38, 0x00, 0x00, 0x00],
+        expected: relative(0x38, -256, -256, 0),
+    },
+    Fixture {
+        name: "relative: primary tap",
+        bytes: [0x09, 0x00, 0x00, 0x00],
+        expected: relative(0x09, 0, 0, 0),
+    },
+    Fixture {
+        name: "relative: secondary tap",
+        bytes: [0x0A, 0x00, 0x00, 0x00],
+        expected: relative(0x0A, 0, 0, 0),
+    },
+    Fixture {
+        name: "relative: aux button",
+        bytes: [0x0C, 0x00, 0x00, 0x00],
+        expected: relative(0x0C, 0, 0, 0),
+    },
+    Fixture {
+        name: "relative: tap while moving",
+        bytes: [0x19, 0xFE, 0x03, 0x00],
+        expected: relative(0x19, -2, 3, 0),
+    },
+    Fixture {
+        name: "relative: IntelliMouse scroll down",
+        bytes: [0x08, 0x00, 0x00, 0x01],
+        expected: relative(0x08, 0, 0, 1),
+    },
+    Fixture {
+        name: "relative: IntelliMouse scroll up",
+        bytes: [0x08, 0x00, 0x00, 0xFF],
+        expected: relative(0x08, 0, 0, -1),
+    },
+    Fixture {
+        name: "relative: IntelliMouse scroll with motion",
+        bytes: [0x28, 0x02, 0xFD, 0xFE],
+        expected: relative(0x28, 2, -3, -2),
+    },
+];
+
+pub(super) const ABSOLUTE: &[Fixture<6, AbsolutePacket>] = &[
+    Fixture {
+        name: "absolute: Z-idle",
+        bytes: [0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
+        expected: absolute(0, 0, 0, 0),
+    },
+    Fixture {
+        name: "absolute: center",
+        bytes: [0x00, 0x00, 0x00, 0x00, 0x34, 0x1A],
+        expected: absolute(0, 1024, 768, 0x1A),
+    },
+    Fixture {
+        name: "absolute: lower bound of the usable area",
+        bytes: [0x00, 0x00, 0x80, 0x40, 0x00, 0x10],
+        expected: absolute(0, 128, 64, 0x10),
+    },
+    Fixture {
+        name: "absolute: upper bound of the usable area",
+        bytes: [0x00, 0x00, 0x80, 0xC0, 0x57, 0x3F],
+        expected: absolute(0, 1920, 1472, 0x3F),
+    },
+    Fixture {
+        name: "absolute: sensor maximum",
+        bytes: [0x00, 0x00, 0xFF, 0xFF, 0x57, 0x08],
+        expected: absolute(0, 2047, 1535, 0x08),
+    },
+    Fixture {
+        name: "absolute: Z upper bits masked",
+        bytes: [0x00, 0x00, 0x10, 0x20, 0x34, 0xFF],
+        expected: absolute(0, 1040, 800, 0x3F),
+    },
+    Fixture {
+        name: "absolute: button pressed",
+        bytes: [0x01, 0x00, 0x00, 0x00, 0x34, 0x20],
+        expected: absolute(0x01, 1024, 768, 0x20),
+    },
+    Fixture {
+        name: "absolute: button upper bits masked",
+        bytes: [0xC3, 0x00, 0x00, 0x00, 0x34, 0x20],
+        expected: absolute(0x03, 1024, 768, 0x20),
+    },
+];
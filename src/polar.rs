@@ -0,0 +1,99 @@
+use crate::{PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER};
+
+/// Center of the usable area of the sensor
+pub(crate) const CENTER: (u16, u16) = (
+    (PINNACLE_X_LOWER + PINNACLE_X_UPPER) / 2,
+    (PINNACLE_Y_LOWER + PINNACLE_Y_UPPER) / 2,
+);
+
+/// Radius of the usable area of the sensor, in X counts
+pub(crate) const MAX_RADIUS: u16 = (PINNACLE_X_UPPER - PINNACLE_X_LOWER) / 2;
+
+/// An absolute position in polar coordinates, see `Tm040040::set_polar_center()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct PolarPosition {
+    /// Distance from the center, in X counts
+    pub radius: u16,
+    /// Angle in 1/65536 of a turn, 0 pointing towards higher X and increasing towards higher Y
+    /// (clockwise, as Y points down)
+    pub angle: u16,
+}
+
+impl PolarPosition {
+    /// Create a polar position
+    pub const fn new(radius: u16, angle: u16) -> Self {
+        Self { radius, angle }
+    }
+
+    /// Center of the usable area of the sensor
+    pub const SENSOR_CENTER: (u16, u16) = CENTER;
+
+    /// Convert an absolute position in sensor axes to polar coordinates around `center`
+    pub fn from_absolute(x: u16, y: u16, center: (u16, u16)) -> Self {
+        let (radius, angle) = to_polar(x, y, center, false);
+
+        Self { radius, angle }
+    }
+}
+
+/// Convert an absolute position to polar coordinates around `center`
+///
+/// Both axes span the diameter of the round sensor with different resolutions, so the axis
+/// with Y resolution (X if `swapped`) is scaled to X counts first. The radius is in X counts,
+/// the angle in 1/65536 of a turn, with 0 pointing towards higher X and increasing towards
+/// higher Y.
+pub(crate) fn to_polar(x: u16, y: u16, center: (u16, u16), swapped: bool) -> (u16, u16) {
+    let scale = |delta: i32| {
+        delta * (PINNACLE_X_UPPER - PINNACLE_X_LOWER) as i32
+            / (PINNACLE_Y_UPPER - PINNACLE_Y_LOWER) as i32
+    };
+    let mut dx = x as i32 - center.0 as i32;
+    let mut dy = y as i32 - center.1 as i32;
+    if swapped {
+        dx = scale(dx);
+    } else {
+        dy = scale(dy);
+    }
+    let radius = isqrt((dx * dx + dy * dy) as u32);
+
+    (radius.min(u16::MAX as u32) as u16, atan2(dy, dx))
+}
+
+/// Integer square root
+fn isqrt(value: u32) -> u32 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting above the root
+    let mut root = value;
+    let mut next = value.div_ceil(2);
+    while next < root {
+        root = next;
+        next = (root + value / root) / 2;
+    }
+
+    root
+}
+
+/// Angle of a vector in 1/65536 of a turn, error below 0.3°
+fn atan2(y: i32, x: i32) -> u16 {
+    if x == 0 && y == 0 {
+        return 0;
+    }
+    let (ax, ay) = (x.unsigned_abs(), y.unsigned_abs());
+    // Angle within the first octant, of the smaller over the larger component
+    let (num, den) = if ax >= ay { (ay, ax) } else { (ax, ay) };
+    let z = ((num as u64) << 16) / den as u64;
+    // atan(z) ≈ π/4·z + 0.273·z·(1 - z), in turns: 8192·z + 2847·z·(1 - z)
+    let octant = ((8192 * z + ((2847 * z * (65536 - z)) >> 16)) >> 16) as u32;
+
+    let first_quadrant = if ax >= ay { octant } else { 16384 - octant };
+    let angle = match (x >= 0, y >= 0) {
+        (true, true) => first_quadrant,
+        (false, true) => 32768 - first_quadrant,
+        (false, false) => 32768 + first_quadrant,
+        (true, false) => 65536 - first_quadrant,
+    };
+
+    angle as u16
+}
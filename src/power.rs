@@ -0,0 +1,169 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    register::Bank0, Error, FeedState, Module, Pinnacle, PositionReportingMode, PowerMode,
+};
+
+/// Hooks invoked around every power mode transition of the touchpad, e.g. to gate the rail of
+/// a pad backlight in sync with sleep and wake, see `Tm040040::set_power_hooks()`.
+///
+/// Transitions are set by `set_power_mode()`, `suspend()`, `resume()`, `auto_power()` and the
+/// wake boost. `from` is `None` until the driver set a power mode. Setting the current power
+/// mode again isn't a transition. Both hooks do nothing by default and should return quickly.
+pub trait PowerHooks {
+    /// The touchpad is about to switch from `from` to `to`, e.g. to power a rail up before waking
+    fn before_transition(&mut self, from: Option<PowerMode>, to: PowerMode) {
+        let _ = (from, to);
+    }
+
+    /// The touchpad switched from `from` to `to`, e.g. to power a rail down after sleeping
+    fn after_transition(&mut self, from: Option<PowerMode>, to: PowerMode) {
+        let _ = (from, to);
+    }
+}
+
+/// Power levels stepped through by `AutoPower`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PowerLevel {
+    /// Sleep mode, the touchpad only checks for touches every 300ms
+    Sleep,
+    /// Normal mode at the idle sample rate
+    Idle,
+    /// Normal mode at the active sample rate
+    Active,
+}
+
+/// Sample rates and timeouts of `AutoPower`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AutoPowerConfig {
+    /// Raw value of the sample rate register while touched
+    pub active_sample_rate: u8,
+    /// Raw value of the sample rate register while idle
+    pub idle_sample_rate: u8,
+    /// Inactivity after which the touchpad steps down to idle, in milliseconds
+    pub idle_after_ms: u32,
+    /// Inactivity after which the touchpad steps down to sleep, in milliseconds
+    pub sleep_after_ms: u32,
+}
+
+impl Default for AutoPowerConfig {
+    fn default() -> Self {
+        Self {
+            active_sample_rate: 100,
+            idle_sample_rate: 40,
+            idle_after_ms: 1_000,
+            sleep_after_ms: 10_000,
+        }
+    }
+}
+
+/// Scales power mode and sample rate with activity, to maximize battery life in handhelds.
+///
+/// Activity raises the touchpad to the active level at once, inactivity steps it down to idle
+/// and then to sleep. Driven by `Tm040040::auto_power()`.
+pub struct AutoPower<'a> {
+    config: AutoPowerConfig,
+    level: Option<PowerLevel>,
+    last_activity_us: Option<u64>,
+    veto: Option<&'a mut dyn FnMut(PowerLevel) -> bool>,
+}
+
+impl<'a> AutoPower<'a> {
+    /// Create a new controller, the level is set on the first update
+    pub fn new(config: AutoPowerConfig) -> Self {
+        Self {
+            config,
+            level: None,
+            last_activity_us: None,
+            veto: None,
+        }
+    }
+
+    /// Get the configured sample rates and timeouts
+    pub fn config(&self) -> &AutoPowerConfig {
+        &self.config
+    }
+
+    /// The current power level, `None` before the first update
+    pub fn level(&self) -> Option<PowerLevel> {
+        self.level
+    }
+
+    /// Set a hook asked before every transition, returning `false` vetoes it
+    /// A vetoed transition is asked for again on the next update.
+    pub fn set_veto(&mut self, veto: &'a mut dyn FnMut(PowerLevel) -> bool) {
+        self.veto = Some(veto);
+    }
+
+    /// Process the activity at `now_us`, returning the level to switch to
+    pub fn update(&mut self, now_us: u64, active: bool) -> Option<PowerLevel> {
+        if active || self.last_activity_us.is_none() {
+            self.last_activity_us = Some(now_us);
+        }
+        let inactive_ms = self
+            .last_activity_us
+            .map_or(0, |last| now_us.saturating_sub(last) / 1000);
+
+        let target = if inactive_ms >= self.config.sleep_after_ms as u64 {
+            PowerLevel::Sleep
+        } else if inactive_ms >= self.config.idle_after_ms as u64 {
+            PowerLevel::Idle
+        } else {
+            PowerLevel::Active
+        };
+        // Step down one level at a time, raise at once
+        let target = match self.level {
+            Some(PowerLevel::Active) if target == PowerLevel::Sleep => PowerLevel::Idle,
+            _ => target,
+        };
+        if self.level == Some(target) {
+            return None;
+        }
+        if let Some(veto) = self.veto.as_mut() {
+            if !veto(target) {
+                return None;
+            }
+        }
+        self.level = Some(target);
+
+        Some(target)
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Update `controller` with the activity at `now_us`, e.g. whether a touching report was
+    /// read, and apply the power level it switches to
+    pub fn auto_power(
+        &mut self,
+        controller: &mut AutoPower<'_>,
+        now_us: u64,
+        active: bool,
+    ) -> Result<Option<PowerLevel>, Error<E, PinError>> {
+        let Some(level) = controller.update(now_us, active) else {
+            return Ok(None);
+        };
+
+        match level {
+            PowerLevel::Active => {
+                self.set_power_mode(PowerMode::Normal)?;
+                self.write_reg(&Bank0::SAMPLE_RATE, controller.config.active_sample_rate)?;
+            }
+            PowerLevel::Idle => {
+                self.set_power_mode(PowerMode::Normal)?;
+                self.write_reg(&Bank0::SAMPLE_RATE, controller.config.idle_sample_rate)?;
+            }
+            PowerLevel::Sleep => self.set_power_mode(PowerMode::Sleep)?,
+        }
+
+        Ok(Some(level))
+    }
+}
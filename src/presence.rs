@@ -0,0 +1,67 @@
+use core::{
+    convert::Infallible,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use embedded_hal::digital::{ErrorType, InputPin};
+
+/// Whether the touchpad is touched, kept up to date by the driver and readable from other
+/// parts of the firmware, e.g. for wake locks or dimming a screen, see
+/// `Tm040040::set_touch_presence()`.
+///
+/// It is updated with every report the driver reads and cleared when the touchpad is suspended.
+/// Relative reports don't carry the touch level, so in relative mode a report with motion, wheel
+/// counts or a pressed button counts as a touch and one without as a lift. A finger resting
+/// without moving sends no reports and keeps the last state.
+pub struct TouchPresence {
+    touched: AtomicBool,
+}
+
+impl TouchPresence {
+    /// Create a presence without a touch
+    pub const fn new() -> Self {
+        Self {
+            touched: AtomicBool::new(false),
+        }
+    }
+
+    /// Whether the touchpad was touched in the latest report
+    pub fn is_touched(&self) -> bool {
+        self.touched.load(Ordering::Relaxed)
+    }
+
+    /// Get a pin reading this presence, high while touched
+    pub fn pin(&self) -> TouchPresencePin<'_> {
+        TouchPresencePin { presence: self }
+    }
+
+    pub(crate) fn set(&self, touched: bool) {
+        self.touched.store(touched, Ordering::Relaxed);
+    }
+}
+
+impl Default for TouchPresence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A `TouchPresence` read like an input pin, high while touched
+#[derive(Clone, Copy)]
+pub struct TouchPresencePin<'a> {
+    presence: &'a TouchPresence,
+}
+
+impl ErrorType for TouchPresencePin<'_> {
+    type Error = Infallible;
+}
+
+impl InputPin for TouchPresencePin<'_> {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.presence.is_touched())
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.presence.is_touched())
+    }
+}
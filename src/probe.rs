@@ -0,0 +1,77 @@
+use core::fmt::Debug;
+
+use embedded_hal::{
+    delay::DelayNs,
+    digital::{self, InputPin},
+    i2c::I2c,
+};
+
+use crate::{
+    error::SensorError, Attached, Error, I2cAddress, Module, MountingOrientation, NoFeed, Pinnacle,
+    PositionMode, Relative, TrackpadConfig, Variant, PINNACLE_FIRMWARE_ID,
+};
+
+/// Result of `probe_and_configure()`
+type Probed<'a, I2C, M, E, PinError> =
+    Result<(Attached<'a, I2C, M, PinError>, ProbeReport), Error<E, PinError>>;
+
+/// What was found and done while bringing up a touchpad, see
+/// `Tm040040::probe_and_configure()`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ProbeReport {
+    /// Firmware ID of the chip
+    pub firmware_id: u8,
+    /// Firmware version of the chip
+    pub firmware_version: u8,
+    /// Touchpad variant of the module
+    pub variant: Variant,
+    /// Time the calibration took, in milliseconds
+    pub calibration_ms: u32,
+    /// Configuration read back after applying it
+    pub config: TrackpadConfig,
+}
+
+impl<'a, I2C, E, M: Module, PinError> Pinnacle<'a, I2C, M, Relative, NoFeed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    /// Create a new trackpad instance like `probe()`, apply `config` and calibrate, e.g. for
+    /// device bring-up.
+    /// Returns a driver in the typestate matching the feed and position mode of `config`,
+    /// along with a report of everything found and applied on the way.
+    pub fn probe_and_configure(
+        i2c: I2C,
+        address: impl Into<I2cAddress>,
+        hardware_data_ready: &'a mut impl InputPin<Error = PinError>,
+        config: &TrackpadConfig,
+        delay: &mut impl DelayNs,
+    ) -> Probed<'a, I2C, M, E, PinError> {
+        let mut trackpad = Self::new(i2c, address, hardware_data_ready);
+        let firmware_id = trackpad.device_id()?;
+        if firmware_id != PINNACLE_FIRMWARE_ID {
+            return Err(Error::SensorError(SensorError::BadChip));
+        }
+        let firmware_version = trackpad.firmware_version()?;
+
+        trackpad.apply_config(config)?;
+        let calibration_ms = trackpad.calibrate_timed(delay)?;
+        let report = ProbeReport {
+            firmware_id,
+            firmware_version,
+            variant: trackpad.variant(),
+            calibration_ms,
+            config: trackpad.config()?,
+        };
+
+        if config.position_mode == PositionMode::Relative {
+            // Axes are only swapped and inverted in hardware in relative mode
+            trackpad.pipeline.orientation =
+                MountingOrientation::from_flags(config.xy_swapped, config.xy_inverted);
+        }
+        let attached = trackpad.into_attached(config.position_mode, config.feed_mode)?;
+
+        Ok((attached, report))
+    }
+}
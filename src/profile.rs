@@ -0,0 +1,251 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    era::Registers, AdcAttenuation, CompensationFlags, Error, FeedState, FilterMode,
+    InvalidPacketAction, Module, PacketValidation, Pinnacle, PositionReportingMode, Thresholds,
+};
+
+/// Sensitivity settings of the glove mode: highest sensitivity and low wide Z minimum
+const GLOVE_THRESHOLDS: Thresholds = Thresholds {
+    adc_attenuation: AdcAttenuation::X1,
+    wide_z_min: 0x01,
+};
+/// Position smoothing of the glove mode, see `set_position_smoothing()`
+const GLOVE_SMOOTHING: u8 = 160;
+/// Software checks of the water rejection profile: droplets and films show up as sudden Z
+/// steps and position jumps, which are dropped rather than reported
+const WATER_VALIDATION: PacketValidation = PacketValidation {
+    check_format: true,
+    max_delta: Some(200),
+    max_z_step: Some(24),
+    max_position_jump: Some(300),
+    action: InvalidPacketAction::Drop,
+};
+
+/// Thickness of the overlay covering the sensor, see `Tm040040::set_overlay_thickness()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum OverlayThickness {
+    /// No overlay, the sensor is touched directly
+    #[default]
+    None,
+    /// 0.5mm overlay
+    HalfMm,
+    /// 1mm overlay
+    OneMm,
+    /// 2mm overlay
+    TwoMm,
+}
+
+/// Material of the overlay covering the sensor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum OverlayMaterial {
+    /// Plastic, e.g. polycarbonate or ABS
+    #[default]
+    Plastic,
+    /// Glass, which couples about as well as plastic of twice the thickness
+    Glass,
+}
+
+impl OverlayThickness {
+    /// Sensitivity settings for an overlay of this thickness and `material`.
+    ///
+    /// Following Cirque's recommendation, thicker overlays get less ADC attenuation (higher
+    /// sensitivity) and a lower wide Z minimum. The values are starting points, tune them on
+    /// the assembled device.
+    pub fn thresholds(self, material: OverlayMaterial) -> Thresholds {
+        // Glass couples better, so it behaves like a thinner plastic overlay
+        let step = match (self, material) {
+            (Self::None, _) => 0,
+            (Self::HalfMm, OverlayMaterial::Glass) => 0,
+            (Self::HalfMm, OverlayMaterial::Plastic) | (Self::OneMm, OverlayMaterial::Glass) => 1,
+            (Self::OneMm, OverlayMaterial::Plastic) | (Self::TwoMm, OverlayMaterial::Glass) => 2,
+            (Self::TwoMm, OverlayMaterial::Plastic) => 3,
+        };
+
+        match step {
+            0 => Thresholds {
+                adc_attenuation: AdcAttenuation::X4,
+                wide_z_min: 0x06,
+            },
+            1 => Thresholds {
+                adc_attenuation: AdcAttenuation::X3,
+                wide_z_min: 0x05,
+            },
+            2 => Thresholds {
+                adc_attenuation: AdcAttenuation::X2,
+                wide_z_min: 0x04,
+            },
+            _ => Thresholds {
+                adc_attenuation: AdcAttenuation::X1,
+                wide_z_min: 0x02,
+            },
+        }
+    }
+}
+
+/// Trade-off between smooth and fast output, setting the hardware filter and the software
+/// position smoothing together, see `Tm040040::set_latency_profile()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum LatencyProfile {
+    /// Hardware filter and software smoothing, for steady cursors
+    Smooth,
+    /// Light software smoothing instead of the hardware filter
+    #[default]
+    Balanced,
+    /// No filtering at all, for the lowest latency
+    Raw,
+}
+
+impl LatencyProfile {
+    /// Hardware filter mode of the profile
+    pub fn filter_mode(self) -> FilterMode {
+        match self {
+            Self::Smooth => FilterMode::Enable,
+            Self::Balanced | Self::Raw => FilterMode::Disable,
+        }
+    }
+
+    /// Software position smoothing of the profile, see `set_position_smoothing()`
+    pub fn smoothing(self) -> u8 {
+        match self {
+            Self::Smooth => 128,
+            Self::Balanced => 96,
+            Self::Raw => 0,
+        }
+    }
+}
+
+/// Settings replaced by the glove mode, restored when it is disabled
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct GloveRestore {
+    adc_attenuation: AdcAttenuation,
+    x_wide_z_min: u8,
+    y_wide_z_min: u8,
+    filter_mode: FilterMode,
+    position_smoothing: u8,
+}
+
+/// Settings replaced by the water rejection profile, restored when it is disabled
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WaterRestore {
+    compensation: CompensationFlags,
+    filter_mode: FilterMode,
+    validation: Option<PacketValidation>,
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Apply the sensitivity settings for an overlay, see `OverlayThickness::thresholds()`
+    /// Temperature compensation, if enabled, overrides these settings when applied.
+    pub fn set_overlay_thickness(
+        &mut self,
+        thickness: OverlayThickness,
+        material: OverlayMaterial,
+    ) -> Result<Thresholds, Error<E, PinError>> {
+        let thresholds = thickness.thresholds(material);
+        self.era_batch(|pad| {
+            pad.set_adc_attenuation(thresholds.adc_attenuation)?;
+            pad.set_wide_z_min(thresholds.wide_z_min)
+        })?;
+
+        Ok(thresholds)
+    }
+
+    /// Set the hardware filter and software smoothing of a latency profile
+    pub fn set_latency_profile(
+        &mut self,
+        profile: LatencyProfile,
+    ) -> Result<(), Error<E, PinError>> {
+        self.update_reg(profile.filter_mode())?;
+        self.pipeline.position_smoothing = profile.smoothing();
+
+        Ok(())
+    }
+
+    /// Enable/disable the glove mode, for devices operated with gloves
+    /// The glove mode raises the sensitivity to the maximum, lowers the wide Z minimum and
+    /// enables hardware filtering and position smoothing against the noisier signal. Disabling
+    /// it restores the previous settings.
+    pub fn set_glove_mode(&mut self, enabled: bool) -> Result<(), Error<E, PinError>> {
+        match (enabled, self.shadows.glove_restore) {
+            (true, None) => {
+                let restore = self.era_batch(|pad| {
+                    let restore = GloveRestore {
+                        adc_attenuation: pad.adc_attenuation()?,
+                        x_wide_z_min: pad.read_era_register(Registers::X_AXIS_WIDE_Z_MIN)?,
+                        y_wide_z_min: pad.read_era_register(Registers::Y_AXIS_WIDE_Z_MIN)?,
+                        filter_mode: pad.filter_mode()?,
+                        position_smoothing: pad.pipeline.position_smoothing,
+                    };
+                    pad.set_adc_attenuation(GLOVE_THRESHOLDS.adc_attenuation)?;
+                    pad.set_wide_z_min(GLOVE_THRESHOLDS.wide_z_min)?;
+
+                    Ok(restore)
+                })?;
+                self.set_filter_mode(FilterMode::Enable)?;
+                self.pipeline.position_smoothing = GLOVE_SMOOTHING;
+                self.shadows.glove_restore = Some(restore);
+            }
+            (false, Some(restore)) => {
+                self.era_batch(|pad| {
+                    pad.set_adc_attenuation(restore.adc_attenuation)?;
+                    pad.write_era_register(Registers::X_AXIS_WIDE_Z_MIN, restore.x_wide_z_min)?;
+                    pad.write_era_register(Registers::Y_AXIS_WIDE_Z_MIN, restore.y_wide_z_min)
+                })?;
+                self.set_filter_mode(restore.filter_mode)?;
+                self.pipeline.position_smoothing = restore.position_smoothing;
+                self.shadows.glove_restore = None;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Whether the glove mode is enabled
+    pub fn is_glove_mode(&self) -> bool {
+        self.shadows.glove_restore.is_some()
+    }
+
+    /// Enable/disable the water rejection profile, for wet or very noisy environments
+    /// The profile enables all hardware compensation and filtering, and drops packets with
+    /// implausible Z steps or position jumps, see `PacketValidation`. The compensation takes
+    /// effect with the next calibration. Disabling it restores the previous settings.
+    pub fn set_water_rejection(&mut self, enabled: bool) -> Result<(), Error<E, PinError>> {
+        match (enabled, self.shadows.water_restore) {
+            (true, None) => {
+                let restore = WaterRestore {
+                    compensation: self.compensation_flags()?,
+                    filter_mode: self.filter_mode()?,
+                    validation: self.pipeline.validation,
+                };
+                self.set_compensation_flags(CompensationFlags::ALL)?;
+                self.set_filter_mode(FilterMode::Enable)?;
+                self.pipeline.validation = Some(WATER_VALIDATION);
+                self.shadows.water_restore = Some(restore);
+            }
+            (false, Some(restore)) => {
+                self.set_compensation_flags(restore.compensation)?;
+                self.set_filter_mode(restore.filter_mode)?;
+                self.pipeline.validation = restore.validation;
+                self.shadows.water_restore = None;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Whether the water rejection profile is enabled
+    pub fn is_water_rejection(&self) -> bool {
+        self.shadows.water_restore.is_some()
+    }
+}
@@ -0,0 +1,157 @@
+use core::{
+    fmt::Debug,
+    sync::atomic::{AtomicU32, AtomicU8, Ordering},
+};
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    Error, FeedState, FilterMode, Module, MountingOrientation, Pinnacle, PositionReportingMode,
+    PowerMode, SpeedPreset, XYEnable,
+};
+
+const SPEED_PRESETS: [SpeedPreset; 4] = [
+    SpeedPreset::Slow,
+    SpeedPreset::Normal,
+    SpeedPreset::Fast,
+    SpeedPreset::Precise,
+];
+
+/// A requested value, with the generations of the last request and the last applied request.
+/// Only loads and stores are used, so this works on targets without compare-and-swap.
+/// Generations are 32 bits wide, so a pending request only compares equal to the applied one
+/// after 2^32 requests between two applies.
+struct Slot {
+    value: AtomicU8,
+    requested: AtomicU32,
+    applied: AtomicU32,
+}
+
+impl Slot {
+    const fn new() -> Self {
+        Self {
+            value: AtomicU8::new(0),
+            requested: AtomicU32::new(0),
+            applied: AtomicU32::new(0),
+        }
+    }
+
+    fn request(&self, value: u8) {
+        self.value.store(value, Ordering::Relaxed);
+        let generation = self.requested.load(Ordering::Relaxed).wrapping_add(1);
+        self.requested.store(generation, Ordering::Release);
+    }
+
+    /// The pending value and its generation, if it wasn't applied yet
+    fn pending(&self) -> Option<(u8, u32)> {
+        let generation = self.requested.load(Ordering::Acquire);
+        if generation == self.applied.load(Ordering::Relaxed) {
+            return None;
+        }
+        // A request arriving in between is applied now and, being idempotent, once more on
+        // the next call
+        Some((self.value.load(Ordering::Relaxed), generation))
+    }
+
+    fn applied(&self, generation: u32) {
+        self.applied.store(generation, Ordering::Relaxed);
+    }
+}
+
+/// Configuration changes requested from one task and applied by the task reading the
+/// touchpad, e.g. in RTIC, so the driver doesn't have to be shared behind a lock.
+///
+/// Changes are requested through a shared reference, typically to a `static`, and applied by
+/// `Tm040040::apply_pending()` at a safe point of the reader's cycle. Only the latest request
+/// per setting is applied. Requests must come from a single task at a time.
+pub struct ConfigProxy {
+    power_mode: Slot,
+    filter_mode: Slot,
+    xy_enable: Slot,
+    orientation: Slot,
+    speed_preset: Slot,
+}
+
+impl ConfigProxy {
+    /// Create a proxy without pending changes
+    pub const fn new() -> Self {
+        Self {
+            power_mode: Slot::new(),
+            filter_mode: Slot::new(),
+            xy_enable: Slot::new(),
+            orientation: Slot::new(),
+            speed_preset: Slot::new(),
+        }
+    }
+
+    /// Request a power mode change
+    pub fn set_power_mode(&self, power_mode: PowerMode) {
+        self.power_mode.request(power_mode as u8);
+    }
+
+    /// Request a filter mode change
+    pub fn set_filter_mode(&self, filter: FilterMode) {
+        self.filter_mode.request(filter as u8);
+    }
+
+    /// Request enabling/disabling axes
+    pub fn set_xy_enable(&self, xy_enable: XYEnable) {
+        self.xy_enable.request(xy_enable as u8);
+    }
+
+    /// Request a mounting orientation change
+    pub fn set_orientation(&self, orientation: MountingOrientation) {
+        self.orientation.request(orientation as u8);
+    }
+
+    /// Request a speed preset change
+    pub fn set_speed_preset(&self, preset: SpeedPreset) {
+        self.speed_preset.request(preset as u8);
+    }
+}
+
+impl Default for ConfigProxy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Apply the changes requested through `proxy` since the last call
+    /// A change that fails to apply stays pending and is retried on the next call.
+    pub fn apply_pending(&mut self, proxy: &ConfigProxy) -> Result<(), Error<E, PinError>> {
+        if let Some((value, generation)) = proxy.power_mode.pending() {
+            self.set_power_mode(PowerMode::try_from(value)?)?;
+            proxy.power_mode.applied(generation);
+        }
+        if let Some((value, generation)) = proxy.filter_mode.pending() {
+            self.set_filter_mode(FilterMode::try_from(value)?)?;
+            proxy.filter_mode.applied(generation);
+        }
+        if let Some((value, generation)) = proxy.xy_enable.pending() {
+            self.set_xy_enable(XYEnable::try_from(value)?)?;
+            proxy.xy_enable.applied(generation);
+        }
+        if let Some((value, generation)) = proxy.orientation.pending() {
+            if let Some(&orientation) = MountingOrientation::ALL.get(value as usize) {
+                self.set_orientation(orientation)?;
+            }
+            proxy.orientation.applied(generation);
+        }
+        if let Some((value, generation)) = proxy.speed_preset.pending() {
+            if let Some(&preset) = SPEED_PRESETS.get(value as usize) {
+                self.set_speed_preset(preset);
+            }
+            proxy.speed_preset.applied(generation);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{AbsoluteData, Gesture, GestureEngine};
+
+/// An absolute report with the time it was read
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecordedReport {
+    /// Time the report was read, in microseconds
+    pub timestamp_us: u64,
+    /// The report
+    pub data: AbsoluteData,
+}
+
+/// Records timestamped reports into a caller-provided buffer, e.g. to tune gesture thresholds
+/// against real user traces on a host machine.
+pub struct Recorder<'b> {
+    buffer: &'b mut [RecordedReport],
+    len: usize,
+}
+
+impl<'b> Recorder<'b> {
+    /// Create a recorder writing into `buffer`
+    pub fn new(buffer: &'b mut [RecordedReport]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    /// Record a report, returning `false` if the buffer is full
+    pub fn record(&mut self, timestamp_us: u64, data: &AbsoluteData) -> bool {
+        let Some(slot) = self.buffer.get_mut(self.len) else {
+            return false;
+        };
+        *slot = RecordedReport {
+            timestamp_us,
+            data: *data,
+        };
+        self.len += 1;
+
+        true
+    }
+
+    /// Whether the buffer is full
+    pub fn is_full(&self) -> bool {
+        self.len == self.buffer.len()
+    }
+
+    /// The reports recorded so far
+    pub fn recording(&self) -> &[RecordedReport] {
+        &self.buffer[..self.len]
+    }
+
+    /// Discard all recorded reports
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+/// Feeds recorded reports through a gesture engine, yielding the detected gestures together
+/// with the timestamp of the report they were detected at.
+pub struct Replayer<'r, 'e, 'g> {
+    reports: core::slice::Iter<'r, RecordedReport>,
+    engine: &'e mut GestureEngine<'g>,
+}
+
+impl<'r, 'e, 'g> Replayer<'r, 'e, 'g> {
+    /// Create a replayer feeding `reports` through `engine`
+    pub fn new(reports: &'r [RecordedReport], engine: &'e mut GestureEngine<'g>) -> Self {
+        Self {
+            reports: reports.iter(),
+            engine,
+        }
+    }
+}
+
+impl Iterator for Replayer<'_, '_, '_> {
+    type Item = (u64, Gesture);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reports.find_map(|report| {
+            self.engine
+                .update(report.timestamp_us, &report.data)
+                .map(|gesture| (report.timestamp_us, gesture))
+        })
+    }
+}
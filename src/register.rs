@@ -27,6 +27,10 @@ pub enum Bank0 {
     PACKET_BYTE3 = 0x15,
     PACKET_BYTE4 = 0x16,
     PACKET_BYTE5 = 0x17,
+    ERA_VALUE = 0x1b,
+    ERA_HIGH_BYTE = 0x1c,
+    ERA_LOW_BYTE = 0x1d,
+    ERA_CONTROL = 0x1e,
 }
 impl Register for Bank0 {
     fn addr(&self) -> u8 {
@@ -36,3 +40,32 @@ impl Register for Bank0 {
         false
     }
 }
+
+/// Registers with a different meaning while the touchpad is in AnyMeas mode.
+/// The layout follows Cirque's AnyMeas example code.
+#[derive(Debug, Clone, Copy)]
+pub enum AnyMeas {
+    ADC_CONFIG1 = 0x05,
+    BIT_LENGTH = 0x06,
+    MUX_CONTROL = 0x07,
+    ADC_CONFIG2 = 0x08,
+    APERTURE_WIDTH = 0x09,
+    RESULT_HIGH = 0x11,
+    RESULT_LOW = 0x12,
+    TOGGLE0 = 0x13,
+    TOGGLE1 = 0x14,
+    TOGGLE2 = 0x15,
+    TOGGLE3 = 0x16,
+    POLARITY0 = 0x17,
+    POLARITY1 = 0x18,
+    POLARITY2 = 0x19,
+    POLARITY3 = 0x1a,
+}
+impl Register for AnyMeas {
+    fn addr(&self) -> u8 {
+        *self as u8
+    }
+    fn read_only(&self) -> bool {
+        matches!(self, Self::RESULT_HIGH | Self::RESULT_LOW)
+    }
+}
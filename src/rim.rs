@@ -0,0 +1,141 @@
+use crate::{
+    polar::{to_polar, CENTER, MAX_RADIUS},
+    AbsoluteData, Variant,
+};
+
+/// Gestures at the rim of the round touchpads, detected by a `RimDetector`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RimGesture {
+    /// A touch started at the rim and moved inwards, e.g. to open a menu from that side.
+    /// The angle is where the touch entered, in 1/65536 of a turn clockwise from higher X.
+    Enter {
+        /// Angle where the touch entered
+        angle: u16,
+    },
+    /// A short touch at the rim, at a clock position from 1 to 12 (12 at lower Y, i.e. the top)
+    Tap {
+        /// Clock position of the tap
+        clock: u8,
+    },
+}
+
+/// Thresholds of the rim gesture detector
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RimConfig {
+    /// Minimum Z level counted as a touch
+    pub touch_z: u8,
+    /// Width of the rim, in X counts from the edge of the usable area
+    pub rim_width: u16,
+    /// Maximum duration of a rim tap, in milliseconds
+    pub tap_max_duration_ms: u32,
+    /// Minimum inward movement of a touch entering from the rim, in X counts
+    pub enter_min_distance: u16,
+}
+
+impl RimConfig {
+    /// Thresholds for the physical size of a variant: a 3mm rim, entering 5mm inwards
+    pub fn for_variant(variant: Variant) -> Self {
+        let counts_per_mm = variant.counts_per_mm();
+
+        Self {
+            touch_z: 4,
+            rim_width: 3 * counts_per_mm,
+            tap_max_duration_ms: 200,
+            enter_min_distance: 5 * counts_per_mm,
+        }
+    }
+}
+
+impl Default for RimConfig {
+    fn default() -> Self {
+        Self::for_variant(Variant::default())
+    }
+}
+
+/// State of the current contact
+#[derive(Clone, Copy, Debug)]
+struct Contact {
+    start_us: u64,
+    start_radius: u16,
+    start_angle: u16,
+    on_rim: bool,
+    entered: bool,
+}
+
+/// Detects touches entering from the rim and taps on the rim of the round touchpads, for
+/// watch-like UIs.
+///
+/// Taps are reported when the finger is lifted, so the touchpad should send Z-idle packets
+/// (the default).
+#[derive(Clone, Copy, Debug)]
+pub struct RimDetector {
+    config: RimConfig,
+    contact: Option<Contact>,
+}
+
+impl RimDetector {
+    /// Create a new rim gesture detector
+    pub fn new(config: RimConfig) -> Self {
+        Self {
+            config,
+            contact: None,
+        }
+    }
+
+    /// Get the configured thresholds
+    pub fn config(&self) -> &RimConfig {
+        &self.config
+    }
+
+    /// Process an absolute report read at `timestamp_us`, returning a detected gesture
+    pub fn update(&mut self, timestamp_us: u64, data: &AbsoluteData) -> Option<RimGesture> {
+        if data.z_level < self.config.touch_z {
+            let contact = self.contact.take()?;
+            let duration_ms = timestamp_us.saturating_sub(contact.start_us) / 1000;
+            if contact.on_rim
+                && !contact.entered
+                && duration_ms <= self.config.tap_max_duration_ms as u64
+            {
+                return Some(RimGesture::Tap {
+                    clock: clock_position(contact.start_angle),
+                });
+            }
+            return None;
+        }
+
+        let (radius, angle) = to_polar(data.x_pos, data.y_pos, CENTER, false);
+        let Some(contact) = self.contact.as_mut() else {
+            self.contact = Some(Contact {
+                start_us: timestamp_us,
+                start_radius: radius,
+                start_angle: angle,
+                on_rim: radius >= MAX_RADIUS.saturating_sub(self.config.rim_width),
+                entered: false,
+            });
+            return None;
+        };
+
+        let inward = contact.start_radius.saturating_sub(radius);
+        if contact.on_rim && !contact.entered && inward >= self.config.enter_min_distance {
+            contact.entered = true;
+            return Some(RimGesture::Enter {
+                angle: contact.start_angle,
+            });
+        }
+
+        None
+    }
+}
+
+/// Clock position of an angle, 12 pointing towards lower Y
+fn clock_position(angle: u16) -> u8 {
+    // Rotate so 0 points towards lower Y, an hour is 1/12 of a turn
+    let from_top = angle.wrapping_add(16384) as u32;
+    let hour = ((from_top * 12 + 32768) >> 16) % 12;
+
+    if hour == 0 {
+        12
+    } else {
+        hour as u8
+    }
+}
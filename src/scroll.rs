@@ -0,0 +1,96 @@
+/// Configuration of scroll output, applying to all scroll-producing features.
+///
+/// A report with wheel counts, and with a clock set every report within `scroll_hold_ms` of
+/// the last one, belongs to a scrolling contact. The arbitration rules apply to these reports
+/// in order: taps starting during the scroll are dropped (`suppress_taps`, buttons held
+/// before stay held), all motion is dropped (`suppress_motion`), or else the minor axis is
+/// dropped (`axis_lock`). Wheel counts short of a detent are carried over to later reports,
+/// with a clock set only until `scroll_hold_ms` passed without wheel counts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScrollConfig {
+    /// Number of IntelliMouse wheel counts per reported detent, 1 reports counts as is
+    pub counts_per_detent: u8,
+    /// Invert the scroll direction (natural scrolling)
+    pub natural: bool,
+    /// Suppress the minor axis while scrolling, e.g. cursor motion across a vertical scroll
+    pub axis_lock: bool,
+    /// Suppress taps during a scrolling contact, so a scroll doesn't also click
+    pub suppress_taps: bool,
+    /// Suppress all cursor motion during a scrolling contact
+    pub suppress_motion: bool,
+    /// Time after the last report with wheel counts the contact still counts as scrolling, in
+    /// milliseconds, requires a clock to be set
+    pub scroll_hold_ms: u16,
+}
+
+impl Default for ScrollConfig {
+    fn default() -> Self {
+        Self {
+            counts_per_detent: 1,
+            natural: false,
+            axis_lock: false,
+            suppress_taps: false,
+            suppress_motion: false,
+            scroll_hold_ms: 100,
+        }
+    }
+}
+
+/// Quantizes wheel counts into whole detents, carrying the remainder over to the next report
+/// so slow scrolling isn't lost
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ScrollAccumulator {
+    config: ScrollConfig,
+    remainder: i16,
+    last_scroll_us: Option<u64>,
+}
+
+impl ScrollAccumulator {
+    pub fn config(&self) -> ScrollConfig {
+        self.config
+    }
+
+    pub fn set_config(&mut self, config: ScrollConfig) {
+        self.config = config;
+        self.remainder = 0;
+        self.last_scroll_us = None;
+    }
+
+    /// Whether a report with the given wheel counts, read at `now_us`, belongs to a scrolling
+    /// contact
+    /// Counts carried over towards a detent are dropped once the scroll hold expired.
+    pub fn scrolling(&mut self, counts: i8, now_us: Option<u64>) -> bool {
+        if counts != 0 {
+            self.last_scroll_us = now_us;
+            return true;
+        }
+
+        match (self.last_scroll_us, now_us) {
+            (Some(last_us), Some(now_us))
+                if now_us.saturating_sub(last_us) <= self.config.scroll_hold_ms as u64 * 1000 =>
+            {
+                true
+            }
+            (Some(_), Some(_)) => {
+                self.last_scroll_us = None;
+                self.remainder = 0;
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Add the wheel counts of a report, returning the number of whole detents
+    pub fn wheel(&mut self, counts: i8) -> i8 {
+        let counts_per_detent = self.config.counts_per_detent.max(1) as i16;
+        let total = self.remainder + counts as i16;
+        self.remainder = total % counts_per_detent;
+
+        let detents = (total / counts_per_detent) as i8;
+        if self.config.natural {
+            detents.saturating_neg()
+        } else {
+            detents
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt::Debug;
+
+use embedded_hal::{delay::DelayNs, digital, i2c::I2c};
+
+use crate::{
+    register::Bank0, CheckResult, FeedMode, FeedState, Module, Pinnacle, PositionMode,
+    PositionReportingMode, PINNACLE_FIRMWARE_ID, PINNACLE_X_MAX, PINNACLE_Y_MAX,
+    RELATIVE_BYTE0_ALWAYS_SET,
+};
+
+/// Value written to Z_IDLE to check that configuration writes are read back correctly
+const READBACK_PATTERN: u8 = 0b0101_0101;
+
+/// Hardware-in-the-loop self test, e.g. for production line testing of assembled devices.
+///
+/// The live report check requires the touchpad to be touched while the test is running.
+#[derive(Clone, Copy, Debug)]
+pub struct SelfTest {
+    /// How long to wait for a live report, in milliseconds
+    pub report_timeout_ms: u32,
+}
+
+/// Results of a `SelfTest` run
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct SelfTestReport {
+    /// The firmware ID read from the touchpad, if it could be read at all
+    pub firmware_id: Option<u8>,
+    /// Whether the firmware ID was read and matches a Pinnacle ASIC
+    pub device_id: CheckResult,
+    /// Whether a configuration register write is read back unchanged
+    pub config_readback: CheckResult,
+    /// Whether a forced calibration completed
+    pub calibration: CheckResult,
+    /// Whether a live report was received within the timeout and is plausible.
+    /// Skipped if feed is disabled.
+    pub live_report: CheckResult,
+}
+
+impl SelfTestReport {
+    /// Whether no check failed
+    pub fn passed(&self) -> bool {
+        ![
+            self.device_id,
+            self.config_readback,
+            self.calibration,
+            self.live_report,
+        ]
+        .contains(&CheckResult::Failed)
+    }
+}
+
+impl Default for SelfTest {
+    fn default() -> Self {
+        Self {
+            report_timeout_ms: 5000,
+        }
+    }
+}
+
+impl SelfTest {
+    /// Run all checks against the touchpad
+    /// Bus errors don't abort the test, they make the affected check fail instead.
+    pub fn run<I2C, E, M: Module, PosMode, Feed, PinError>(
+        &self,
+        pad: &mut Pinnacle<'_, I2C, M, PosMode, Feed, PinError>,
+        delay: &mut impl DelayNs,
+    ) -> SelfTestReport
+    where
+        I2C: I2c<Error = E>,
+        E: Debug,
+        PosMode: PositionReportingMode,
+        Feed: FeedState,
+        PinError: digital::Error,
+    {
+        let mut report = SelfTestReport {
+            firmware_id: pad.device_id().ok(),
+            ..Default::default()
+        };
+
+        report.device_id = (report.firmware_id == Some(PINNACLE_FIRMWARE_ID)).into();
+        if report.firmware_id.is_none() {
+            // Nothing else will work without communication
+            return report;
+        }
+        report.config_readback = Self::check_config_readback(pad);
+        report.calibration = pad.calibrate(delay).is_ok().into();
+        report.live_report = self.check_live_report(pad, delay);
+
+        report
+    }
+
+    fn check_config_readback<I2C, E, M: Module, PosMode, Feed, PinError>(
+        pad: &mut Pinnacle<'_, I2C, M, PosMode, Feed, PinError>,
+    ) -> CheckResult
+    where
+        I2C: I2c<Error = E>,
+        E: Debug,
+        PosMode: PositionReportingMode,
+        Feed: FeedState,
+        PinError: digital::Error,
+    {
+        let Ok(original) = pad.read_reg(&Bank0::Z_IDLE) else {
+            return CheckResult::Failed;
+        };
+        let readback = pad
+            .write_reg(&Bank0::Z_IDLE, READBACK_PATTERN)
+            .and_then(|_| pad.read_reg(&Bank0::Z_IDLE));
+        let restored = pad.write_reg(&Bank0::Z_IDLE, original);
+
+        match (readback, restored) {
+            (Ok(READBACK_PATTERN), Ok(())) => CheckResult::Passed,
+            _ => CheckResult::Failed,
+        }
+    }
+
+    fn check_live_report<I2C, E, M: Module, PosMode, Feed, PinError>(
+        &self,
+        pad: &mut Pinnacle<'_, I2C, M, PosMode, Feed, PinError>,
+        delay: &mut impl DelayNs,
+    ) -> CheckResult
+    where
+        I2C: I2c<Error = E>,
+        E: Debug,
+        PosMode: PositionReportingMode,
+        Feed: FeedState,
+        PinError: digital::Error,
+    {
+        match pad.feed_mode() {
+            Ok(FeedMode::Enabled) => (),
+            Ok(FeedMode::NoFeed) => return CheckResult::Skipped,
+            Err(_) => return CheckResult::Failed,
+        }
+        let Ok(position_mode) = pad.position_mode() else {
+            return CheckResult::Failed;
+        };
+
+        for _ in 0..self.report_timeout_ms {
+            match pad.data_ready() {
+                Ok(true) => (),
+                Ok(false) => {
+                    delay.delay_ms(1);
+                    continue;
+                }
+                Err(_) => return CheckResult::Failed,
+            }
+
+            let mut packet = [0u8; 6];
+            if pad.read_regs(&Bank0::PACKET_BYTE0, &mut packet).is_err()
+                || pad.finish_report().is_err()
+            {
+                return CheckResult::Failed;
+            }
+
+            return if Self::packet_plausible(position_mode, &packet) {
+                CheckResult::Passed
+            } else {
+                CheckResult::Failed
+            };
+        }
+
+        CheckResult::Failed
+    }
+
+    /// Check the packet against the fixed parts of the packet format
+    fn packet_plausible(position_mode: PositionMode, packet: &[u8; 6]) -> bool {
+        match position_mode {
+            PositionMode::Relative => packet[0] & RELATIVE_BYTE0_ALWAYS_SET != 0,
+            PositionMode::Absolute => {
+                let x = packet[2] as u16 | (((packet[4] & 0x0F) as u16) << 8);
+                let y = packet[3] as u16 | (((packet[4] & 0xF0) as u16) << 4);
+                x <= PINNACLE_X_MAX && y <= PINNACLE_Y_MAX
+            }
+        }
+    }
+}
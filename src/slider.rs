@@ -0,0 +1,268 @@
+use crate::{AbsoluteData, PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER};
+
+/// Axis absolute touches are projected onto by a `Slider`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SliderAxis {
+    /// Along X, from the lowest to the highest position
+    Horizontal,
+    /// Along Y, from the lowest to the highest position
+    Vertical,
+    /// Along an arbitrary line between two absolute positions, from the first to the second
+    Line((u16, u16), (u16, u16)),
+}
+
+impl SliderAxis {
+    fn endpoints(self) -> ((i64, i64), (i64, i64)) {
+        let x_mid = (PINNACLE_X_LOWER + PINNACLE_X_UPPER) as i64 / 2;
+        let y_mid = (PINNACLE_Y_LOWER + PINNACLE_Y_UPPER) as i64 / 2;
+        match self {
+            Self::Horizontal => (
+                (PINNACLE_X_LOWER as i64, y_mid),
+                (PINNACLE_X_UPPER as i64, y_mid),
+            ),
+            Self::Vertical => (
+                (x_mid, PINNACLE_Y_LOWER as i64),
+                (x_mid, PINNACLE_Y_UPPER as i64),
+            ),
+            Self::Line(from, to) => ((from.0 as i64, from.1 as i64), (to.0 as i64, to.1 as i64)),
+        }
+    }
+}
+
+/// Configuration of a `Slider`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliderConfig {
+    /// Axis touches are projected onto
+    pub axis: SliderAxis,
+    /// Minimum Z level counted as a touch
+    pub touch_z: u8,
+    /// Number of detents the value snaps to, evenly spaced including both ends, `None` for a
+    /// continuous value
+    pub detents: Option<u16>,
+}
+
+impl Default for SliderConfig {
+    fn default() -> Self {
+        Self {
+            axis: SliderAxis::Horizontal,
+            touch_z: 4,
+            detents: None,
+        }
+    }
+}
+
+/// Uses the touchpad as a one-dimensional slider, e.g. for volume or brightness strips.
+///
+/// Touches are projected onto the configured axis and output as a value from 0 to
+/// `u16::MAX`.
+#[derive(Clone, Copy, Debug)]
+pub struct Slider {
+    config: SliderConfig,
+    value: Option<u16>,
+}
+
+impl Slider {
+    /// Create a new slider
+    pub fn new(config: SliderConfig) -> Self {
+        Self {
+            config,
+            value: None,
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &SliderConfig {
+        &self.config
+    }
+
+    /// The last value, `None` if the slider wasn't touched yet
+    pub fn value(&self) -> Option<u16> {
+        self.value
+    }
+
+    /// Process an absolute report, returning the new value if it changed
+    /// With detents, the value only changes when another detent is reached.
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<u16> {
+        if data.z_level < self.config.touch_z {
+            return None;
+        }
+
+        let value = self.snap(self.project(data.x_pos, data.y_pos));
+        if self.value == Some(value) {
+            return None;
+        }
+        self.value = Some(value);
+
+        Some(value)
+    }
+
+    /// The detent of a value, `None` without detents
+    pub fn detent(&self, value: u16) -> Option<u16> {
+        let step = self.detent_step()?;
+
+        Some(((value as u32 + step / 2) / step) as u16)
+    }
+
+    /// Distance between detents
+    fn detent_step(&self) -> Option<u32> {
+        let detents = self.config.detents?.max(2) as u32;
+
+        Some(u16::MAX as u32 / (detents - 1))
+    }
+
+    /// Position of a touch along the axis
+    fn project(&self, x: u16, y: u16) -> u16 {
+        let ((x0, y0), (x1, y1)) = self.config.axis.endpoints();
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let length_sq = dx * dx + dy * dy;
+        if length_sq == 0 {
+            return 0;
+        }
+        let dot = (x as i64 - x0) * dx + (y as i64 - y0) * dy;
+
+        (dot.clamp(0, length_sq) * u16::MAX as i64 / length_sq) as u16
+    }
+
+    /// Snap a value to the nearest detent, if configured
+    fn snap(&self, value: u16) -> u16 {
+        match (self.detent(value), self.detent_step()) {
+            (Some(detent), Some(step)) => (detent as u32 * step) as u16,
+            _ => value,
+        }
+    }
+}
+
+/// How a `SliderPair` splits the touchpad into two parallel lanes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum LaneSplit {
+    /// Two vertical lanes side by side, the first one at lower X positions
+    #[default]
+    Vertical,
+    /// Two horizontal lanes on top of each other, the first one at lower Y positions
+    Horizontal,
+}
+
+/// A lane of a `SliderPair`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lane {
+    /// The lane at lower positions
+    First,
+    /// The lane at higher positions
+    Second,
+}
+
+/// Configuration of a `SliderPair`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SliderPairConfig {
+    /// How the touchpad is split into lanes
+    pub split: LaneSplit,
+    /// Width of the dead band between the lanes, in counts, touches starting in it are ignored
+    pub gap: u16,
+    /// Minimum Z level counted as a touch
+    pub touch_z: u8,
+    /// Number of detents the values snap to, `None` for continuous values
+    pub detents: Option<u16>,
+}
+
+impl Default for SliderPairConfig {
+    fn default() -> Self {
+        Self {
+            split: LaneSplit::Vertical,
+            gap: 128,
+            touch_z: 4,
+            detents: None,
+        }
+    }
+}
+
+/// Uses the touchpad as two parallel sliders with independent values, e.g. for mixer-style
+/// controls operated by two thumbs.
+///
+/// The lane of a touch is picked at touchdown and kept until the finger is lifted, so a finger
+/// drifting across the gap doesn't move the other slider. The touchpad reports a single
+/// position, so only one lane can be operated at a time; reports with multiple fingers
+/// detected are ignored.
+#[derive(Clone, Copy, Debug)]
+pub struct SliderPair {
+    config: SliderPairConfig,
+    lanes: [Slider; 2],
+    touched: Option<Lane>,
+}
+
+impl SliderPair {
+    /// Create a new slider pair
+    pub fn new(config: SliderPairConfig) -> Self {
+        let axis = match config.split {
+            LaneSplit::Vertical => SliderAxis::Vertical,
+            LaneSplit::Horizontal => SliderAxis::Horizontal,
+        };
+        let lane = Slider::new(SliderConfig {
+            axis,
+            touch_z: config.touch_z,
+            detents: config.detents,
+        });
+
+        Self {
+            config,
+            lanes: [lane; 2],
+            touched: None,
+        }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &SliderPairConfig {
+        &self.config
+    }
+
+    /// The last value of a lane, `None` if it wasn't touched yet
+    pub fn value(&self, lane: Lane) -> Option<u16> {
+        self.lanes[lane as usize].value()
+    }
+
+    /// Whether a lane is touched
+    pub fn is_touched(&self, lane: Lane) -> bool {
+        self.touched == Some(lane)
+    }
+
+    /// Process an absolute report, returning the lane and its new value if it changed
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<(Lane, u16)> {
+        if data.z_level < self.config.touch_z {
+            self.touched = None;
+            return None;
+        }
+        if data.multi_finger {
+            return None;
+        }
+
+        let lane = match self.touched {
+            Some(lane) => lane,
+            None => {
+                let lane = self.lane_at(data.x_pos, data.y_pos)?;
+                self.touched = Some(lane);
+                lane
+            }
+        };
+
+        self.lanes[lane as usize]
+            .update(data)
+            .map(|value| (lane, value))
+    }
+
+    /// The lane containing a position, `None` in the gap
+    fn lane_at(&self, x: u16, y: u16) -> Option<Lane> {
+        let (position, lower, upper) = match self.config.split {
+            LaneSplit::Vertical => (x, PINNACLE_X_LOWER, PINNACLE_X_UPPER),
+            LaneSplit::Horizontal => (y, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER),
+        };
+        let middle = (lower + upper) / 2;
+        let half_gap = self.config.gap / 2;
+
+        if position < middle.saturating_sub(half_gap) {
+            Some(Lane::First)
+        } else if position > middle.saturating_add(half_gap) {
+            Some(Lane::Second)
+        } else {
+            None
+        }
+    }
+}
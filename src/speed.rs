@@ -0,0 +1,63 @@
+/// Cursor speed presets applied to relative output, akin to mouse DPI switching.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum SpeedPreset {
+    /// Half speed
+    Slow,
+    /// Deltas as reported by the touchpad
+    #[default]
+    Normal,
+    /// Double speed
+    Fast,
+    /// Quarter speed, for precise positioning
+    Precise,
+}
+
+impl SpeedPreset {
+    /// The gain of this preset as numerator and denominator
+    pub fn gain(self) -> (i16, i16) {
+        match self {
+            Self::Slow => (1, 2),
+            Self::Normal => (1, 1),
+            Self::Fast => (2, 1),
+            Self::Precise => (1, 4),
+        }
+    }
+
+    /// The next preset when cycling through presets
+    pub fn next(self) -> Self {
+        match self {
+            Self::Slow => Self::Normal,
+            Self::Normal => Self::Fast,
+            Self::Fast => Self::Precise,
+            Self::Precise => Self::Slow,
+        }
+    }
+}
+
+/// Applies the gain of a speed preset to relative deltas, carrying the remainder of
+/// fractional gains over to the next report so slow movements aren't lost
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct SpeedScaler {
+    preset: SpeedPreset,
+    remainder: (i16, i16),
+}
+
+impl SpeedScaler {
+    pub fn preset(&self) -> SpeedPreset {
+        self.preset
+    }
+
+    pub fn set_preset(&mut self, preset: SpeedPreset) {
+        self.preset = preset;
+        self.remainder = (0, 0);
+    }
+
+    pub fn scale(&mut self, x_delta: i16, y_delta: i16) -> (i16, i16) {
+        let (num, den) = self.preset.gain();
+        let x = x_delta.saturating_mul(num).saturating_add(self.remainder.0);
+        let y = y_delta.saturating_mul(num).saturating_add(self.remainder.1);
+        self.remainder = (x % den, y % den);
+
+        (x / den, y / den)
+    }
+}
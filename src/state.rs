@@ -0,0 +1,190 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::{
+    bus::{BusHealth, BusProfile},
+    button::ButtonDecoupler,
+    clock::{Clock, StaleReportPolicy},
+    compensation::{TemperatureCompensator, TemperatureSource},
+    config_audit::{ConfigAudit, FeedConfigShadow},
+    data_ready::{DataReadySignal, DataReadySource, PinErrorPolicy},
+    events::EventSink,
+    frame_sync::FrameSync,
+    haptics::{Edge, HapticHooks},
+    mode_check::ModeCheck,
+    multi_finger::MultiFingerDetector,
+    orientation::{CoordinateOrigin, MountingOrientation},
+    power::PowerHooks,
+    presence::TouchPresence,
+    profile::{GloveRestore, WaterRestore},
+    scroll::ScrollAccumulator,
+    speed::{SpeedPreset, SpeedScaler},
+    timings::Timings,
+    traffic::TrafficMeter,
+    transform::Transform,
+    validation::PacketValidation,
+    zones::Region,
+    FeedMode, IntelliMouseMode, PowerMode, TrackpadConfig, XYEnable,
+};
+
+/// Bus access, data ready line handling and timing
+pub(crate) struct BusState<'a, E> {
+    pub(crate) data_ready: DataReadySource<'a, E>,
+    pub(crate) bus_profile: BusProfile,
+    pub(crate) bus_health: BusHealth,
+    pub(crate) timings: Timings,
+    pub(crate) dr_signal: DataReadySignal,
+    pub(crate) dr_recheck: bool,
+    pub(crate) dr_pending: bool,
+    pub(crate) dr_handshake: bool,
+    pub(crate) pin_error_policy: PinErrorPolicy,
+    pub(crate) dr_degraded: bool,
+    pub(crate) lazy_clear: bool,
+    pub(crate) flags_pending: bool,
+    pub(crate) report_confirmed: bool,
+    pub(crate) combined_reads: bool,
+    pub(crate) combined_packet: Option<[u8; 6]>,
+    pub(crate) era_batch: bool,
+    pub(crate) era_bank: Option<u8>,
+    pub(crate) clock: Option<&'a mut dyn Clock>,
+    pub(crate) delay: Option<&'a mut dyn DelayNs>,
+    pub(crate) last_clear_us: Option<u64>,
+    pub(crate) max_report_age_us: Option<u32>,
+    pub(crate) stale_policy: StaleReportPolicy,
+    pub(crate) traffic: Option<TrafficMeter>,
+}
+
+impl<'a, E> BusState<'a, E> {
+    pub(crate) fn new(data_ready: DataReadySource<'a, E>) -> Self {
+        Self {
+            data_ready,
+            bus_profile: BusProfile::Standard,
+            bus_health: BusHealth::default(),
+            timings: Timings::default(),
+            dr_signal: DataReadySignal::Level,
+            dr_recheck: false,
+            dr_pending: false,
+            dr_handshake: false,
+            pin_error_policy: PinErrorPolicy::Fail,
+            dr_degraded: false,
+            lazy_clear: false,
+            flags_pending: false,
+            report_confirmed: false,
+            combined_reads: false,
+            combined_packet: None,
+            era_batch: false,
+            era_bank: None,
+            clock: None,
+            delay: None,
+            last_clear_us: None,
+            max_report_age_us: None,
+            stale_policy: StaleReportPolicy::Tag,
+            traffic: None,
+        }
+    }
+}
+
+/// Processing of reports after they are read
+pub(crate) struct Pipeline<'a> {
+    pub(crate) orientation: MountingOrientation,
+    pub(crate) origin: CoordinateOrigin,
+    pub(crate) tap_exclusions: &'a [Region],
+    pub(crate) position_estimate: Option<(u16, u16)>,
+    pub(crate) transform: Option<&'a dyn Transform>,
+    pub(crate) polar_center: Option<(u16, u16)>,
+    pub(crate) position_smoothing: u8,
+    pub(crate) auto_software_filter: bool,
+    pub(crate) smoothed_position: Option<(u16, u16, u8)>,
+    pub(crate) discard_reports: u8,
+    pub(crate) discard_remaining: u8,
+    pub(crate) validation: Option<PacketValidation>,
+    pub(crate) last_absolute: Option<(u16, u16, u8)>,
+    pub(crate) multi_finger: Option<MultiFingerDetector>,
+    pub(crate) button_decoupler: Option<ButtonDecoupler>,
+    pub(crate) speed: SpeedScaler,
+    pub(crate) scroll: ScrollAccumulator,
+    pub(crate) last_buttons: u8,
+    pub(crate) last_edge: Option<Edge>,
+    pub(crate) temperature_compensation: Option<TemperatureCompensator>,
+    pub(crate) sequence: u32,
+}
+
+impl Default for Pipeline<'_> {
+    fn default() -> Self {
+        Self {
+            orientation: MountingOrientation::Normal,
+            origin: CoordinateOrigin::Native,
+            tap_exclusions: &[],
+            position_estimate: None,
+            transform: None,
+            polar_center: None,
+            position_smoothing: 0,
+            auto_software_filter: true,
+            smoothed_position: None,
+            discard_reports: 0,
+            discard_remaining: 0,
+            validation: None,
+            last_absolute: None,
+            multi_finger: None,
+            button_decoupler: None,
+            speed: SpeedScaler::default(),
+            scroll: ScrollAccumulator::default(),
+            last_buttons: 0,
+            last_edge: None,
+            temperature_compensation: None,
+            sequence: 0,
+        }
+    }
+}
+
+/// Power mode transitions and suspended or interrupted states
+#[derive(Default)]
+pub(crate) struct PowerState {
+    pub(crate) wake_boost: Option<u8>,
+    pub(crate) sleeping: bool,
+    pub(crate) last_power_mode: Option<PowerMode>,
+    pub(crate) suspended: Option<TrackpadConfig>,
+    pub(crate) calibrating: Option<FeedMode>,
+}
+
+/// Configuration written to the touchpad, kept to restore or check it
+pub(crate) struct ConfigShadows {
+    pub(crate) intelli_mouse: IntelliMouseMode,
+    pub(crate) xy_enable: XYEnable,
+    pub(crate) glove_restore: Option<GloveRestore>,
+    pub(crate) water_restore: Option<WaterRestore>,
+    pub(crate) mode_check: Option<ModeCheck>,
+    pub(crate) reports_since_mode_check: u16,
+    pub(crate) config_audit: Option<ConfigAudit>,
+    pub(crate) feed_config_shadow: FeedConfigShadow,
+    pub(crate) reports_since_audit: u16,
+    pub(crate) last_audit_us: Option<u64>,
+}
+
+impl Default for ConfigShadows {
+    fn default() -> Self {
+        Self {
+            intelli_mouse: IntelliMouseMode::Disabled,
+            xy_enable: XYEnable::Enabled,
+            glove_restore: None,
+            water_restore: None,
+            mode_check: None,
+            reports_since_mode_check: 0,
+            config_audit: None,
+            feed_config_shadow: FeedConfigShadow::default(),
+            reports_since_audit: 0,
+            last_audit_us: None,
+        }
+    }
+}
+
+/// Hooks and sinks set by the application
+#[derive(Default)]
+pub(crate) struct Hooks<'a> {
+    pub(crate) speed_hook: Option<&'a mut dyn FnMut(SpeedPreset)>,
+    pub(crate) haptics: Option<&'a mut dyn HapticHooks>,
+    pub(crate) power_hooks: Option<&'a mut dyn PowerHooks>,
+    pub(crate) temperature_source: Option<&'a mut dyn TemperatureSource>,
+    pub(crate) event_sink: Option<&'a mut dyn EventSink>,
+    pub(crate) frame_sync: Option<&'a mut dyn FrameSync>,
+    pub(crate) touch_presence: Option<&'a TouchPresence>,
+}
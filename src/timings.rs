@@ -0,0 +1,27 @@
+/// Protocol timing of the touchpad, see `Tm040040::set_timings()`.
+///
+/// The defaults fit genuine Cirque modules. Some clone modules need longer settle times.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Timings {
+    /// Time the touchpad needs to start up after leaving shutdown, in milliseconds
+    pub wake_ms: u32,
+    /// Maximum time to wait for a calibration to complete, in milliseconds
+    pub calibration_timeout_ms: u32,
+    /// Maximum time to wait for an AnyMeas measurement to complete, in milliseconds
+    pub measurement_timeout_ms: u32,
+    /// Time to wait after each register write before the next transaction, in microseconds
+    /// Waiting requires a delay to be set, see `Tm040040::set_delay()`, register writes
+    /// fail with `SensorError::DelayRequired` without one, before anything is written.
+    pub post_write_us: u32,
+}
+
+impl Default for Timings {
+    fn default() -> Self {
+        Self {
+            wake_ms: 50,
+            calibration_timeout_ms: 500,
+            measurement_timeout_ms: 50,
+            post_write_us: 0,
+        }
+    }
+}
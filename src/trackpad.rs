@@ -0,0 +1,164 @@
+use core::fmt::Debug;
+
+use embedded_hal::{delay::DelayNs, digital, i2c::I2c};
+
+#[cfg(feature = "absolute")]
+use crate::Absolute;
+#[cfg(any(feature = "absolute", feature = "relative"))]
+use crate::FeedEnabled;
+#[cfg(feature = "relative")]
+use crate::Relative;
+use crate::{
+    AbsoluteData, Error, FeedState, FilterMode, Module, MountingOrientation, Pinnacle,
+    PositionReportingMode, PowerMode, RelativeData, TrackpadConfig, XYEnable,
+};
+
+/// The high-level configuration surface of a touchpad, implemented by all `Pinnacle` drivers.
+///
+/// Application code written against this (and `RelativeTrackpad`/`AbsoluteTrackpad`) instead of
+/// a concrete driver can be unit tested with a mock touchpad. All methods are object safe.
+pub trait Trackpad {
+    /// Error returned by all operations
+    type Error;
+
+    /// Read the firmware ID of the touchpad
+    fn device_id(&mut self) -> Result<u8, Self::Error>;
+
+    /// Read the current configuration of the touchpad
+    fn config(&mut self) -> Result<TrackpadConfig, Self::Error>;
+
+    /// Get the power mode
+    fn power_mode(&mut self) -> Result<PowerMode, Self::Error>;
+
+    /// Set the power mode
+    fn set_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Self::Error>;
+
+    /// Get the filter mode
+    fn filter_mode(&mut self) -> Result<FilterMode, Self::Error>;
+
+    /// Set the filter mode
+    fn set_filter_mode(&mut self, filter: FilterMode) -> Result<(), Self::Error>;
+
+    /// Get the enabled axes
+    fn xy_enable(&mut self) -> Result<XYEnable, Self::Error>;
+
+    /// Set the enabled axes
+    fn set_xy_enable(&mut self, xy_enable: XYEnable) -> Result<(), Self::Error>;
+
+    /// Get the mounting orientation
+    fn orientation(&self) -> MountingOrientation;
+
+    /// Set the mounting orientation
+    fn set_orientation(&mut self, orientation: MountingOrientation) -> Result<(), Self::Error>;
+
+    /// Force a calibration of the touchpad
+    fn calibrate(&mut self, delay: &mut dyn DelayNs) -> Result<(), Self::Error>;
+
+    /// Put the touchpad into shutdown, keeping its configuration
+    fn suspend(&mut self) -> Result<(), Self::Error>;
+
+    /// Wake the touchpad from `suspend()`, restoring its configuration
+    fn resume(&mut self, delay: &mut dyn DelayNs) -> Result<(), Self::Error>;
+}
+
+/// A touchpad reporting relative data
+pub trait RelativeTrackpad: Trackpad {
+    /// Read the next report, `None` if no new data is available
+    fn relative_data(&mut self) -> Result<Option<RelativeData>, Self::Error>;
+}
+
+/// A touchpad reporting absolute data
+pub trait AbsoluteTrackpad: Trackpad {
+    /// Read the next report, `None` if no new data is available
+    fn absolute_data(&mut self) -> Result<Option<AbsoluteData>, Self::Error>;
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Trackpad
+    for Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    type Error = Error<E, PinError>;
+
+    fn device_id(&mut self) -> Result<u8, Self::Error> {
+        Pinnacle::device_id(self)
+    }
+
+    fn config(&mut self) -> Result<TrackpadConfig, Self::Error> {
+        Pinnacle::config(self)
+    }
+
+    fn power_mode(&mut self) -> Result<PowerMode, Self::Error> {
+        Pinnacle::power_mode(self)
+    }
+
+    fn set_power_mode(&mut self, power_mode: PowerMode) -> Result<(), Self::Error> {
+        Pinnacle::set_power_mode(self, power_mode)
+    }
+
+    fn filter_mode(&mut self) -> Result<FilterMode, Self::Error> {
+        Pinnacle::filter_mode(self)
+    }
+
+    fn set_filter_mode(&mut self, filter: FilterMode) -> Result<(), Self::Error> {
+        Pinnacle::set_filter_mode(self, filter)
+    }
+
+    fn xy_enable(&mut self) -> Result<XYEnable, Self::Error> {
+        Pinnacle::xy_enable(self)
+    }
+
+    fn set_xy_enable(&mut self, xy_enable: XYEnable) -> Result<(), Self::Error> {
+        Pinnacle::set_xy_enable(self, xy_enable)
+    }
+
+    fn orientation(&self) -> MountingOrientation {
+        Pinnacle::orientation(self)
+    }
+
+    fn set_orientation(&mut self, orientation: MountingOrientation) -> Result<(), Self::Error> {
+        Pinnacle::set_orientation(self, orientation)
+    }
+
+    fn calibrate(&mut self, mut delay: &mut dyn DelayNs) -> Result<(), Self::Error> {
+        Pinnacle::calibrate(self, &mut delay)
+    }
+
+    fn suspend(&mut self) -> Result<(), Self::Error> {
+        Pinnacle::suspend(self)
+    }
+
+    fn resume(&mut self, mut delay: &mut dyn DelayNs) -> Result<(), Self::Error> {
+        Pinnacle::resume(self, &mut delay)
+    }
+}
+
+#[cfg(feature = "relative")]
+impl<I2C, E, M: Module, PinError> RelativeTrackpad
+    for Pinnacle<'_, I2C, M, Relative, FeedEnabled, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    fn relative_data(&mut self) -> Result<Option<RelativeData>, Self::Error> {
+        Pinnacle::relative_data(self)
+    }
+}
+
+#[cfg(feature = "absolute")]
+impl<I2C, E, M: Module, PinError> AbsoluteTrackpad
+    for Pinnacle<'_, I2C, M, Absolute, FeedEnabled, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PinError: digital::Error,
+{
+    fn absolute_data(&mut self) -> Result<Option<AbsoluteData>, Self::Error> {
+        Pinnacle::absolute_data(self)
+    }
+}
@@ -0,0 +1,401 @@
+use core::fmt::Debug;
+
+use embedded_hal::{digital, i2c::I2c};
+
+use crate::{
+    compensation::TRACK_ADC_CONFIG_ATTENUATION,
+    config::Bitfield,
+    era::Registers,
+    register::{Bank0, Register},
+    AdcAttenuation, Error, FeedMode, FeedState, FilterMode, GlideExtendMode, IntelliMouseMode,
+    Module, Pinnacle, PositionMode, PositionReportingMode, PowerMode, ScrollMode, TapMode,
+    XYEnable, XYInverted, XYSwapped,
+};
+
+/// The configuration of the touchpad, as read by `Tm040040::config()`.
+///
+/// Axis swapping and inversion are the hardware settings, which are only used in relative
+/// mode, see `Tm040040::set_orientation()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrackpadConfig {
+    /// Power mode
+    pub power_mode: PowerMode,
+    /// Feed mode
+    pub feed_mode: FeedMode,
+    /// Position reporting mode
+    pub position_mode: PositionMode,
+    /// Filter mode
+    pub filter_mode: FilterMode,
+    /// Enabled axes
+    pub xy_enable: XYEnable,
+    /// Hardware axis inversion
+    pub xy_inverted: XYInverted,
+    /// Hardware axis swapping
+    pub xy_swapped: XYSwapped,
+    /// IntelliMouse mode
+    pub intelli_mouse: IntelliMouseMode,
+    /// Tap mode
+    pub tap_mode: TapMode,
+    /// Scroll mode
+    pub scroll_mode: ScrollMode,
+    /// Glide extend mode
+    pub glide_extend: GlideExtendMode,
+    /// Raw value of the sample rate register
+    pub sample_rate: u8,
+    /// Number of Z-idle packets sent after the finger is lifted
+    pub z_idle: u8,
+    /// Raw value of the Z scaler register
+    pub z_scaler: u8,
+    /// Raw value of the sleep interval register
+    pub sleep_interval: u8,
+    /// Raw value of the sleep timer register
+    pub sleep_timer: u8,
+    /// Attenuation of the tracking ADC
+    pub adc_attenuation: AdcAttenuation,
+    /// Minimum Z level of wide touches on the X axis
+    pub x_wide_z_min: u8,
+    /// Minimum Z level of wide touches on the Y axis
+    pub y_wide_z_min: u8,
+    /// Number of reports discarded after enabling feed, calibrating or resuming, a driver
+    /// setting rather than a register
+    pub discard_reports: u8,
+}
+
+/// A register field of `TrackpadConfig`, as listed in a `ConfigMismatch`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConfigField {
+    PowerMode,
+    FeedMode,
+    PositionMode,
+    FilterMode,
+    XYEnable,
+    XYInverted,
+    XYSwapped,
+    IntelliMouse,
+    TapMode,
+    ScrollMode,
+    GlideExtend,
+    SampleRate,
+    ZIdle,
+    ZScaler,
+    SleepInterval,
+    SleepTimer,
+    AdcAttenuation,
+    XWideZMin,
+    YWideZMin,
+}
+
+/// Address of a register holding a configuration field
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigRegister {
+    /// Register of bank 0
+    Bank0(u8),
+    /// Extended register, accessed through ERA
+    Extended(u16),
+}
+
+impl ConfigField {
+    /// All fields, in the order of `TrackpadConfig`
+    pub const ALL: [Self; 19] = [
+        Self::PowerMode,
+        Self::FeedMode,
+        Self::PositionMode,
+        Self::FilterMode,
+        Self::XYEnable,
+        Self::XYInverted,
+        Self::XYSwapped,
+        Self::IntelliMouse,
+        Self::TapMode,
+        Self::ScrollMode,
+        Self::GlideExtend,
+        Self::SampleRate,
+        Self::ZIdle,
+        Self::ZScaler,
+        Self::SleepInterval,
+        Self::SleepTimer,
+        Self::AdcAttenuation,
+        Self::XWideZMin,
+        Self::YWideZMin,
+    ];
+
+    /// Name of the field in `TrackpadConfig`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::PowerMode => "power_mode",
+            Self::FeedMode => "feed_mode",
+            Self::PositionMode => "position_mode",
+            Self::FilterMode => "filter_mode",
+            Self::XYEnable => "xy_enable",
+            Self::XYInverted => "xy_inverted",
+            Self::XYSwapped => "xy_swapped",
+            Self::IntelliMouse => "intelli_mouse",
+            Self::TapMode => "tap_mode",
+            Self::ScrollMode => "scroll_mode",
+            Self::GlideExtend => "glide_extend",
+            Self::SampleRate => "sample_rate",
+            Self::ZIdle => "z_idle",
+            Self::ZScaler => "z_scaler",
+            Self::SleepInterval => "sleep_interval",
+            Self::SleepTimer => "sleep_timer",
+            Self::AdcAttenuation => "adc_attenuation",
+            Self::XWideZMin => "x_wide_z_min",
+            Self::YWideZMin => "y_wide_z_min",
+        }
+    }
+
+    /// Register holding the field
+    pub fn register(self) -> ConfigRegister {
+        match self {
+            Self::PowerMode => bank0::<PowerMode>(),
+            Self::FeedMode => bank0::<FeedMode>(),
+            Self::PositionMode => bank0::<PositionMode>(),
+            Self::FilterMode => bank0::<FilterMode>(),
+            Self::XYEnable => bank0::<XYEnable>(),
+            Self::XYInverted => bank0::<XYInverted>(),
+            Self::XYSwapped => bank0::<XYSwapped>(),
+            Self::IntelliMouse => bank0::<IntelliMouseMode>(),
+            Self::TapMode => bank0::<TapMode>(),
+            Self::ScrollMode => bank0::<ScrollMode>(),
+            Self::GlideExtend => bank0::<GlideExtendMode>(),
+            Self::SampleRate => ConfigRegister::Bank0(Bank0::SAMPLE_RATE.addr()),
+            Self::ZIdle => ConfigRegister::Bank0(Bank0::Z_IDLE.addr()),
+            Self::ZScaler => ConfigRegister::Bank0(Bank0::Z_SCALER.addr()),
+            Self::SleepInterval => ConfigRegister::Bank0(Bank0::SLEEP_INTERVAL.addr()),
+            Self::SleepTimer => ConfigRegister::Bank0(Bank0::SLEEP_TIMER.addr()),
+            Self::AdcAttenuation => ConfigRegister::Extended(Registers::TRACK_ADC_CONFIG.addr()),
+            Self::XWideZMin => ConfigRegister::Extended(Registers::X_AXIS_WIDE_Z_MIN.addr()),
+            Self::YWideZMin => ConfigRegister::Extended(Registers::Y_AXIS_WIDE_Z_MIN.addr()),
+        }
+    }
+
+    /// Bits of the register holding the field
+    pub fn mask(self) -> u8 {
+        match self {
+            Self::PowerMode => PowerMode::BITMASK,
+            Self::FeedMode => FeedMode::BITMASK,
+            Self::PositionMode => PositionMode::BITMASK,
+            Self::FilterMode => FilterMode::BITMASK,
+            Self::XYEnable => XYEnable::BITMASK,
+            Self::XYInverted => XYInverted::BITMASK,
+            Self::XYSwapped => XYSwapped::BITMASK,
+            Self::IntelliMouse => IntelliMouseMode::BITMASK,
+            Self::TapMode => TapMode::BITMASK,
+            Self::ScrollMode => ScrollMode::BITMASK,
+            Self::GlideExtend => GlideExtendMode::BITMASK,
+            Self::AdcAttenuation => TRACK_ADC_CONFIG_ATTENUATION,
+            _ => 0xFF,
+        }
+    }
+}
+
+fn bank0<BF: Bitfield<Reg = Bank0>>() -> ConfigRegister {
+    ConfigRegister::Bank0(BF::REGISTER.addr())
+}
+
+/// Fields read back differently than expected, returned by `Tm040040::verify_config()`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub struct ConfigMismatch {
+    /// One bit per field, in the order of `ConfigField::ALL`
+    fields: u32,
+}
+
+impl ConfigMismatch {
+    /// Whether `field` differs
+    pub fn contains(&self, field: ConfigField) -> bool {
+        self.fields & (1 << field as u32) != 0
+    }
+
+    /// The fields that differ, use `ConfigField::register()` and `ConfigField::mask()` to
+    /// locate them
+    pub fn fields(&self) -> impl Iterator<Item = ConfigField> + '_ {
+        ConfigField::ALL
+            .into_iter()
+            .filter(|field| self.contains(*field))
+    }
+
+    /// Whether no field differs
+    pub fn is_empty(&self) -> bool {
+        self.fields == 0
+    }
+
+    /// The fields as bits, in the order of `ConfigField::ALL`
+    pub fn bits(&self) -> u32 {
+        self.fields
+    }
+
+    /// Create from the fields as bits, ignoring bits without a field
+    pub fn from_bits(bits: u32) -> Self {
+        Self {
+            fields: bits & ((1 << ConfigField::ALL.len()) - 1),
+        }
+    }
+
+    pub(crate) fn check(&mut self, field: ConfigField, matches: bool) {
+        if !matches {
+            self.fields |= 1 << field as u32;
+        }
+    }
+}
+
+impl<I2C, E, M: Module, PosMode, Feed, PinError> Pinnacle<'_, I2C, M, PosMode, Feed, PinError>
+where
+    I2C: I2c<Error = E>,
+    E: Debug,
+    PosMode: PositionReportingMode,
+    Feed: FeedState,
+    PinError: digital::Error,
+{
+    /// Read the current configuration of the touchpad
+    pub fn config(&mut self) -> Result<TrackpadConfig, Error<E, PinError>> {
+        let (adc_attenuation, x_wide_z_min, y_wide_z_min) = self.era_batch(|pad| {
+            Ok((
+                pad.adc_attenuation()?,
+                pad.read_era_register(Registers::X_AXIS_WIDE_Z_MIN)?,
+                pad.read_era_register(Registers::Y_AXIS_WIDE_Z_MIN)?,
+            ))
+        })?;
+
+        Ok(TrackpadConfig {
+            power_mode: self.read_field()?,
+            feed_mode: self.read_field()?,
+            position_mode: self.read_field()?,
+            filter_mode: self.read_field()?,
+            xy_enable: self.read_field()?,
+            xy_inverted: self.read_field()?,
+            xy_swapped: self.read_field()?,
+            intelli_mouse: self.read_field()?,
+            tap_mode: self.read_field()?,
+            scroll_mode: self.read_field()?,
+            glide_extend: self.read_field()?,
+            sample_rate: self.read_reg(&Bank0::SAMPLE_RATE)?,
+            z_idle: self.read_reg(&Bank0::Z_IDLE)?,
+            z_scaler: self.read_reg(&Bank0::Z_SCALER)?,
+            sleep_interval: self.read_reg(&Bank0::SLEEP_INTERVAL)?,
+            sleep_timer: self.read_reg(&Bank0::SLEEP_TIMER)?,
+            adc_attenuation,
+            x_wide_z_min,
+            y_wide_z_min,
+            discard_reports: self.pipeline.discard_reports,
+        })
+    }
+
+    /// Read back every register of a configuration and compare it to `expected`, e.g. to
+    /// debug devices behaving differently after a field update.
+    ///
+    /// Returns `Error::ConfigMismatch` listing the differing fields. `discard_reports` is a
+    /// driver setting and not compared.
+    pub fn verify_config(&mut self, expected: &TrackpadConfig) -> Result<(), Error<E, PinError>> {
+        let actual = self.config()?;
+        let mut mismatch = ConfigMismatch::default();
+        mismatch.check(
+            ConfigField::PowerMode,
+            actual.power_mode == expected.power_mode,
+        );
+        mismatch.check(
+            ConfigField::FeedMode,
+            actual.feed_mode == expected.feed_mode,
+        );
+        mismatch.check(
+            ConfigField::PositionMode,
+            actual.position_mode == expected.position_mode,
+        );
+        mismatch.check(
+            ConfigField::FilterMode,
+            actual.filter_mode == expected.filter_mode,
+        );
+        mismatch.check(
+            ConfigField::XYEnable,
+            actual.xy_enable == expected.xy_enable,
+        );
+        mismatch.check(
+            ConfigField::XYInverted,
+            actual.xy_inverted == expected.xy_inverted,
+        );
+        mismatch.check(
+            ConfigField::XYSwapped,
+            actual.xy_swapped == expected.xy_swapped,
+        );
+        mismatch.check(
+            ConfigField::IntelliMouse,
+            actual.intelli_mouse == expected.intelli_mouse,
+        );
+        mismatch.check(ConfigField::TapMode, actual.tap_mode == expected.tap_mode);
+        mismatch.check(
+            ConfigField::ScrollMode,
+            actual.scroll_mode == expected.scroll_mode,
+        );
+        mismatch.check(
+            ConfigField::GlideExtend,
+            actual.glide_extend == expected.glide_extend,
+        );
+        mismatch.check(
+            ConfigField::SampleRate,
+            actual.sample_rate == expected.sample_rate,
+        );
+        mismatch.check(ConfigField::ZIdle, actual.z_idle == expected.z_idle);
+        mismatch.check(ConfigField::ZScaler, actual.z_scaler == expected.z_scaler);
+        mismatch.check(
+            ConfigField::SleepInterval,
+            actual.sleep_interval == expected.sleep_interval,
+        );
+        mismatch.check(
+            ConfigField::SleepTimer,
+            actual.sleep_timer == expected.sleep_timer,
+        );
+        mismatch.check(
+            ConfigField::AdcAttenuation,
+            actual.adc_attenuation == expected.adc_attenuation,
+        );
+        mismatch.check(
+            ConfigField::XWideZMin,
+            actual.x_wide_z_min == expected.x_wide_z_min,
+        );
+        mismatch.check(
+            ConfigField::YWideZMin,
+            actual.y_wide_z_min == expected.y_wide_z_min,
+        );
+
+        if mismatch.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::ConfigMismatch(mismatch))
+        }
+    }
+
+    /// Write a configuration to the touchpad
+    /// Feed and position mode must match the typestate of the driver.
+    pub(crate) fn apply_config(
+        &mut self,
+        config: &TrackpadConfig,
+    ) -> Result<(), Error<E, PinError>> {
+        // Extended registers first, accessing them pauses feed
+        self.era_batch(|pad| {
+            pad.set_adc_attenuation(config.adc_attenuation)?;
+            pad.write_era_register(Registers::X_AXIS_WIDE_Z_MIN, config.x_wide_z_min)?;
+            pad.write_era_register(Registers::Y_AXIS_WIDE_Z_MIN, config.y_wide_z_min)
+        })?;
+
+        self.write_reg(&Bank0::SAMPLE_RATE, config.sample_rate)?;
+        self.write_reg(&Bank0::Z_IDLE, config.z_idle)?;
+        self.write_reg(&Bank0::Z_SCALER, config.z_scaler)?;
+        self.write_reg(&Bank0::SLEEP_INTERVAL, config.sleep_interval)?;
+        self.write_reg(&Bank0::SLEEP_TIMER, config.sleep_timer)?;
+
+        self.update_reg(config.intelli_mouse)?;
+        self.shadows.intelli_mouse = config.intelli_mouse;
+        self.update_reg(config.tap_mode)?;
+        self.update_reg(config.scroll_mode)?;
+        self.update_reg(config.glide_extend)?;
+        self.update_reg(config.xy_swapped)?;
+        self.update_reg(config.position_mode)?;
+        self.update_reg(config.filter_mode)?;
+        self.update_reg(config.xy_enable)?;
+        self.update_reg(config.xy_inverted)?;
+        self.update_reg(config.feed_mode)?;
+        self.switch_power_mode(config.power_mode)?;
+        self.shadows.xy_enable = config.xy_enable;
+        self.pipeline.discard_reports = config.discard_reports;
+
+        Ok(())
+    }
+}
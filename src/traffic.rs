@@ -0,0 +1,51 @@
+/// Length of the accounting window
+const WINDOW_US: u64 = 1_000_000;
+
+/// Accounting of the bytes transferred on the bus per second, with an optional budget.
+///
+/// Bytes are counted in one second windows of the clock, without a clock nothing is
+/// accounted. Only payload bytes are counted, not address bytes.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct TrafficMeter {
+    budget: Option<u32>,
+    window_start_us: Option<u64>,
+    window_bytes: u32,
+    bytes_per_s: Option<u32>,
+}
+
+impl TrafficMeter {
+    pub fn budget(&self) -> Option<u32> {
+        self.budget
+    }
+
+    pub fn set_budget(&mut self, bytes_per_s: Option<u32>) {
+        self.budget = bytes_per_s;
+    }
+
+    /// Bytes transferred in the last complete window
+    pub fn bytes_per_s(&self) -> Option<u32> {
+        self.bytes_per_s
+    }
+
+    /// Whether the budget is used up for the current window
+    pub fn over_budget(&self) -> bool {
+        self.budget
+            .is_some_and(|budget| self.window_bytes >= budget)
+    }
+
+    pub fn record(&mut self, now_us: Option<u64>, bytes: usize) {
+        let Some(now_us) = now_us else {
+            return;
+        };
+        let start_us = *self.window_start_us.get_or_insert(now_us);
+        let elapsed_us = now_us.saturating_sub(start_us);
+        if elapsed_us >= WINDOW_US {
+            // Scaled, as windows only end with the first transfer after a second
+            let bytes_per_s = self.window_bytes as u64 * WINDOW_US / elapsed_us;
+            self.bytes_per_s = Some(bytes_per_s as u32);
+            self.window_start_us = Some(now_us);
+            self.window_bytes = 0;
+        }
+        self.window_bytes = self.window_bytes.saturating_add(bytes as u32);
+    }
+}
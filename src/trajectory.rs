@@ -0,0 +1,140 @@
+use heapless::Deque;
+
+use crate::AbsoluteData;
+
+/// Minimum Z level counted as a touch
+const TOUCH_Z: u8 = 4;
+/// Minimum extent (on either axis) of a shape
+const MIN_SHAPE_SIZE: u16 = 200;
+/// Minimum movement (on the X axis) counted as a stroke of a 'Z'
+const MIN_STROKE: u16 = 100;
+
+/// Shapes recognized by the shape matcher
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Shape {
+    /// A roughly straight stroke
+    Line,
+    /// A closed stroke with similar width and height
+    Circle,
+    /// Left-to-right, back diagonally down-left, then left-to-right again
+    Z,
+}
+
+/// Records the most recent absolute points of the current touch, for recognizing shapes drawn
+/// on the pad, e.g. for unlock or shortcut gestures.
+///
+/// Only the last `N` points are kept, so `N` should cover the duration of the longest shape at
+/// the configured sample rate.
+#[derive(Clone, Debug, Default)]
+pub struct Trajectory<const N: usize> {
+    points: Deque<(u16, u16), N>,
+}
+
+impl<const N: usize> Trajectory<N> {
+    /// Create an empty trajectory
+    pub fn new() -> Self {
+        Self {
+            points: Deque::new(),
+        }
+    }
+
+    /// Process the next absolute report
+    /// Points are recorded while the pad is touched. When the finger is lifted, the recorded
+    /// trajectory is matched, cleared and the recognized shape returned.
+    pub fn update(&mut self, data: &AbsoluteData) -> Option<Shape> {
+        if data.z_level >= TOUCH_Z {
+            if self.points.is_full() {
+                self.points.pop_front();
+            }
+            // Can't fail, there is room after removing the oldest point
+            let _ = self.points.push_back((data.x_pos, data.y_pos));
+
+            return None;
+        }
+
+        let shape = self.match_shape();
+        self.clear();
+
+        shape
+    }
+
+    /// The recorded points, oldest first
+    pub fn points(&self) -> impl Iterator<Item = &(u16, u16)> {
+        self.points.iter()
+    }
+
+    /// Discard all recorded points
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// Match the recorded trajectory against the known shapes
+    pub fn match_shape(&self) -> Option<Shape> {
+        let &start = self.points.front()?;
+        let &end = self.points.back()?;
+
+        let (mut min_x, mut max_x, mut min_y, mut max_y) = (start.0, start.0, start.1, start.1);
+        let mut path = 0u32;
+        let mut last = start;
+        for &point in self.points.iter() {
+            min_x = min_x.min(point.0);
+            max_x = max_x.max(point.0);
+            min_y = min_y.min(point.1);
+            max_y = max_y.max(point.1);
+            path += distance(last, point);
+            last = point;
+        }
+
+        let (width, height) = (max_x - min_x, max_y - min_y);
+        if width.max(height) < MIN_SHAPE_SIZE {
+            return None;
+        }
+
+        let chord = distance(start, end);
+        if chord * 10 >= path * 9 {
+            return Some(Shape::Line);
+        }
+        if chord * 5 <= path && width <= height * 2 && height <= width * 2 {
+            return Some(Shape::Circle);
+        }
+        if end.1 > start.1 && self.x_strokes() == 3 {
+            return Some(Shape::Z);
+        }
+
+        None
+    }
+
+    /// Count the strokes alternating in X direction, starting with one to the right
+    fn x_strokes(&self) -> u8 {
+        let mut strokes = 0;
+        let mut anchor = match self.points.front() {
+            Some(&(x, _)) => x,
+            None => return 0,
+        };
+        for &(x, _) in self.points.iter() {
+            let rightwards = strokes % 2 == 0;
+            let moved = if rightwards {
+                x.saturating_sub(anchor)
+            } else {
+                anchor.saturating_sub(x)
+            };
+            if moved >= MIN_STROKE {
+                strokes += 1;
+                anchor = x;
+            } else if rightwards == (x < anchor) {
+                // Follow the previous stroke to its turning point
+                anchor = x;
+            }
+        }
+
+        strokes
+    }
+}
+
+/// Approximate euclidean distance of two points, without square roots
+fn distance(a: (u16, u16), b: (u16, u16)) -> u32 {
+    let dx = a.0.abs_diff(b.0) as u32;
+    let dy = a.1.abs_diff(b.1) as u32;
+
+    dx.max(dy) + dx.min(dy) * 3 / 8
+}
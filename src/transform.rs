@@ -0,0 +1,301 @@
+/// Fixed-point one in the Q16.16 format used by transform coefficients
+pub const Q16_ONE: i32 = 1 << 16;
+
+/// A coordinate transform applied to absolute positions, after the mounting orientation.
+///
+/// Use it to correct the geometry of curved overlays or oddly mounted pads, or to map
+/// positions to a different coordinate space like screen pixels.
+pub trait Transform {
+    /// Transform a position
+    fn apply(&self, x: i32, y: i32) -> (i32, i32);
+}
+
+/// Apply two transforms in sequence, first `.0` then `.1`
+impl<A: Transform, B: Transform> Transform for (A, B) {
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        let (x, y) = self.0.apply(x, y);
+        self.1.apply(x, y)
+    }
+}
+
+/// Rotation around a center point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rotation {
+    /// Center of the rotation
+    pub center: (i32, i32),
+    /// Cosine of the angle, in Q16.16
+    pub cos: i32,
+    /// Sine of the angle, in Q16.16
+    pub sin: i32,
+}
+
+impl Rotation {
+    /// Rotation by `degrees` (clockwise in touchpad coordinates, where Y points down)
+    /// around `center`
+    pub fn degrees(degrees: i32, center: (i32, i32)) -> Self {
+        Self {
+            center,
+            cos: sin_q16(degrees + 90),
+            sin: sin_q16(degrees),
+        }
+    }
+}
+
+impl Transform for Rotation {
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        let dx = (x - self.center.0) as i64;
+        let dy = (y - self.center.1) as i64;
+        let (cos, sin) = (self.cos as i64, self.sin as i64);
+
+        (
+            self.center.0 + ((dx * cos - dy * sin) >> 16) as i32,
+            self.center.1 + ((dx * sin + dy * cos) >> 16) as i32,
+        )
+    }
+}
+
+/// Sine of an angle in degrees in Q16.16, using Bhaskara I's approximation (error below 0.2%)
+fn sin_q16(degrees: i32) -> i32 {
+    let degrees = degrees.rem_euclid(360) as i64;
+    let (degrees, sign) = if degrees < 180 {
+        (degrees, 1)
+    } else {
+        (degrees - 180, -1)
+    };
+    let product = degrees * (180 - degrees);
+
+    sign * ((4 * product * Q16_ONE as i64) / (40500 - product)) as i32
+}
+
+/// Mirroring across vertical and/or horizontal lines through a center point
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Mirror {
+    /// Center of the mirroring
+    pub center: (i32, i32),
+    /// Mirror X positions
+    pub x: bool,
+    /// Mirror Y positions
+    pub y: bool,
+}
+
+impl Transform for Mirror {
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        (
+            if self.x { 2 * self.center.0 - x } else { x },
+            if self.y { 2 * self.center.1 - y } else { y },
+        )
+    }
+}
+
+/// Affine transform with Q16.16 coefficients:
+/// `x' = a * x + b * y + c`, `y' = d * x + e * y + f`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Affine {
+    pub a: i32,
+    pub b: i32,
+    pub c: i32,
+    pub d: i32,
+    pub e: i32,
+    pub f: i32,
+}
+
+impl Affine {
+    /// The identity transform
+    pub const IDENTITY: Self = Self {
+        a: Q16_ONE,
+        b: 0,
+        c: 0,
+        d: 0,
+        e: Q16_ONE,
+        f: 0,
+    };
+
+    /// Affine transform mapping three `measured` points onto the `target` points
+    /// Returns `None` if the measured points are collinear or the coefficients overflow.
+    pub fn from_points(measured: [(i32, i32); 3], target: [(i32, i32); 3]) -> Option<Self> {
+        let h = Mat3::triangle(target).mul(&Mat3::triangle(measured).adjugate()?);
+        let h = h.normalized()?;
+
+        Some(Self {
+            a: fixed(h.0[0][0], 16)?,
+            b: fixed(h.0[0][1], 16)?,
+            c: fixed(h.0[0][2], 16)?,
+            d: fixed(h.0[1][0], 16)?,
+            e: fixed(h.0[1][1], 16)?,
+            f: fixed(h.0[1][2], 16)?,
+        })
+    }
+}
+
+impl Transform for Affine {
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        let (x, y) = (x as i64, y as i64);
+
+        (
+            ((self.a as i64 * x + self.b as i64 * y + self.c as i64) >> 16) as i32,
+            ((self.d as i64 * x + self.e as i64 * y + self.f as i64) >> 16) as i32,
+        )
+    }
+}
+
+/// Perspective (keystone) transform with Q16.16 coefficients:
+/// `x' = (a * x + b * y + c) / w`, `y' = (d * x + e * y + f) / w` with `w = g * x + h * y + 1`
+///
+/// `g` and `h` are usually tiny, so they're stored in Q8.24 instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Keystone {
+    pub a: i32,
+    pub b: i32,
+    pub c: i32,
+    pub d: i32,
+    pub e: i32,
+    pub f: i32,
+    /// In Q8.24
+    pub g: i32,
+    /// In Q8.24
+    pub h: i32,
+}
+
+impl Keystone {
+    /// Perspective transform mapping the four `measured` corners onto the `target` corners
+    ///
+    /// Corners are ordered top left, top right, bottom right, bottom left. The coefficients are
+    /// computed once in floating point, evaluation is fixed point only.
+    /// Returns `None` if the corners are degenerate or the coefficients overflow.
+    pub fn from_corners(measured: [(i32, i32); 4], target: [(i32, i32); 4]) -> Option<Self> {
+        let h = Mat3::square_to_quad(target)?.mul(&Mat3::square_to_quad(measured)?.adjugate()?);
+        let h = h.normalized()?;
+
+        Some(Self {
+            a: fixed(h.0[0][0], 16)?,
+            b: fixed(h.0[0][1], 16)?,
+            c: fixed(h.0[0][2], 16)?,
+            d: fixed(h.0[1][0], 16)?,
+            e: fixed(h.0[1][1], 16)?,
+            f: fixed(h.0[1][2], 16)?,
+            g: fixed(h.0[2][0], 24)?,
+            h: fixed(h.0[2][1], 24)?,
+        })
+    }
+}
+
+impl Transform for Keystone {
+    fn apply(&self, x: i32, y: i32) -> (i32, i32) {
+        let (x, y) = (x as i64, y as i64);
+        // In Q16.16, like the numerators
+        let w = ((self.g as i64 * x + self.h as i64 * y) >> 8) + Q16_ONE as i64;
+        if w <= 0 {
+            // Beyond the horizon of the transform
+            return (x as i32, y as i32);
+        }
+
+        (
+            ((self.a as i64 * x + self.b as i64 * y + self.c as i64) / w) as i32,
+            ((self.d as i64 * x + self.e as i64 * y + self.f as i64) / w) as i32,
+        )
+    }
+}
+
+/// Convert to fixed point with `shift` fractional bits, rounding to nearest
+fn fixed(value: f64, shift: u32) -> Option<i32> {
+    let scaled = value * (1u64 << shift) as f64;
+    if !(i32::MIN as f64..=i32::MAX as f64).contains(&scaled) {
+        return None;
+    }
+
+    Some(if scaled >= 0.0 {
+        (scaled + 0.5) as i32
+    } else {
+        (scaled - 0.5) as i32
+    })
+}
+
+/// Homogeneous 2D transform matrix, only used to compute coefficients
+struct Mat3([[f64; 3]; 3]);
+
+impl Mat3 {
+    /// Affine map of the unit triangle (0,0), (1,0), (0,1) onto `points`
+    fn triangle(points: [(i32, i32); 3]) -> Self {
+        let [(x0, y0), (x1, y1), (x2, y2)] = points.map(|(x, y)| (x as f64, y as f64));
+
+        Self([
+            [x1 - x0, x2 - x0, x0],
+            [y1 - y0, y2 - y0, y0],
+            [0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// Projective map of the unit square onto the quadrilateral `corners`, see Heckbert,
+    /// "Fundamentals of Texture Mapping and Image Warping"
+    fn square_to_quad(corners: [(i32, i32); 4]) -> Option<Self> {
+        let [(x0, y0), (x1, y1), (x2, y2), (x3, y3)] = corners.map(|(x, y)| (x as f64, y as f64));
+        let sx = x0 - x1 + x2 - x3;
+        let sy = y0 - y1 + y2 - y3;
+        let (dx1, dx2, dy1, dy2) = (x1 - x2, x3 - x2, y1 - y2, y3 - y2);
+        let det = dx1 * dy2 - dx2 * dy1;
+        if det == 0.0 {
+            return None;
+        }
+        let g = (sx * dy2 - dx2 * sy) / det;
+        let h = (dx1 * sy - sx * dy1) / det;
+
+        Some(Self([
+            [x1 - x0 + g * x1, x3 - x0 + h * x3, x0],
+            [y1 - y0 + g * y1, y3 - y0 + h * y3, y0],
+            [g, h, 1.0],
+        ]))
+    }
+
+    /// Adjugate, the inverse up to scale, or `None` if the matrix is singular
+    fn adjugate(&self) -> Option<Self> {
+        let m = &self.0;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| {
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let adj = Self([
+            [
+                cofactor(1, 2, 1, 2),
+                -cofactor(0, 2, 1, 2),
+                cofactor(0, 1, 1, 2),
+            ],
+            [
+                -cofactor(1, 2, 0, 2),
+                cofactor(0, 2, 0, 2),
+                -cofactor(0, 1, 0, 2),
+            ],
+            [
+                cofactor(1, 2, 0, 1),
+                -cofactor(0, 2, 0, 1),
+                cofactor(0, 1, 0, 1),
+            ],
+        ]);
+        let det = m[0][0] * adj.0[0][0] + m[0][1] * adj.0[1][0] + m[0][2] * adj.0[2][0];
+
+        (det != 0.0).then_some(adj)
+    }
+
+    fn mul(&self, other: &Self) -> Self {
+        let mut product = [[0.0; 3]; 3];
+        for (r, row) in product.iter_mut().enumerate() {
+            for (c, value) in row.iter_mut().enumerate() {
+                *value = (0..3).map(|k| self.0[r][k] * other.0[k][c]).sum();
+            }
+        }
+
+        Self(product)
+    }
+
+    /// Scale so the bottom right element is 1
+    fn normalized(mut self) -> Option<Self> {
+        let scale = self.0[2][2];
+        if scale == 0.0 {
+            return None;
+        }
+        self.0
+            .iter_mut()
+            .flatten()
+            .for_each(|value| *value /= scale);
+
+        Some(self)
+    }
+}
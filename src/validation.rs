@@ -0,0 +1,83 @@
+use crate::{PINNACLE_X_MAX, PINNACLE_Y_MAX, RELATIVE_BYTE0_ALWAYS_SET};
+
+/// What to do with packets failing validation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum InvalidPacketAction {
+    /// Return `Error::InvalidPacket`
+    #[default]
+    Error,
+    /// Silently drop the packet, as if there was no data
+    Drop,
+}
+
+/// Plausibility checks for decoded packets, protecting downstream consumers from corrupted
+/// data, e.g. due to I²C glitches on long cables.
+///
+/// Packets failing validation are still consumed, so the next report is read normally.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PacketValidation {
+    /// Check that absolute positions are within the sensor range and that the fixed bits of
+    /// relative packets are set
+    pub check_format: bool,
+    /// Maximum magnitude of relative X/Y deltas
+    pub max_delta: Option<u16>,
+    /// Maximum change of the Z level between two consecutive absolute reports
+    pub max_z_step: Option<u8>,
+    /// Maximum change of the absolute position (on either axis) between two consecutive
+    /// touching reports, rejecting implausible jumps e.g. caused by moisture
+    pub max_position_jump: Option<u16>,
+    /// How packets failing validation are handled
+    pub action: InvalidPacketAction,
+}
+
+impl Default for PacketValidation {
+    fn default() -> Self {
+        Self {
+            check_format: true,
+            max_delta: Some(200),
+            max_z_step: Some(40),
+            max_position_jump: None,
+            action: InvalidPacketAction::Error,
+        }
+    }
+}
+
+impl PacketValidation {
+    /// Check a relative packet, given the first packet byte and the decoded deltas
+    pub(crate) fn relative_valid(&self, byte0: u8, x_delta: i16, y_delta: i16) -> bool {
+        if self.check_format && byte0 & RELATIVE_BYTE0_ALWAYS_SET == 0 {
+            return false;
+        }
+
+        self.max_delta.is_none_or(|max_delta| {
+            x_delta.unsigned_abs() <= max_delta && y_delta.unsigned_abs() <= max_delta
+        })
+    }
+
+    /// Check an absolute packet, given the raw (unclamped) position and Z level of this and
+    /// the previous packet
+    pub(crate) fn absolute_valid(
+        &self,
+        x: u16,
+        y: u16,
+        z: u8,
+        last: Option<(u16, u16, u8)>,
+    ) -> bool {
+        if self.check_format && (x > PINNACLE_X_MAX || y > PINNACLE_Y_MAX) {
+            return false;
+        }
+        let Some((last_x, last_y, last_z)) = last else {
+            return true;
+        };
+
+        let z_valid = self
+            .max_z_step
+            .is_none_or(|max_z_step| z.abs_diff(last_z) <= max_z_step);
+        let touching = z > 0 && last_z > 0;
+        let position_valid = self.max_position_jump.is_none_or(|max_jump| {
+            !touching || (x.abs_diff(last_x) <= max_jump && y.abs_diff(last_y) <= max_jump)
+        });
+
+        z_valid && position_valid
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{PINNACLE_X_LOWER, PINNACLE_X_UPPER, PINNACLE_Y_LOWER, PINNACLE_Y_UPPER};
+
+/// A Pinnacle touchpad module, pinning the geometry and defaults of a `Pinnacle` driver.
+///
+/// Supporting a new module only requires a marker type implementing this, see `modules`.
+/// The module can't be detected: the ASIC is shared by all modules and no documented register
+/// identifies the sensor it is mounted on, so the module is always chosen by the driver type.
+pub trait Module {
+    /// Variant of the module
+    const VARIANT: Variant;
+}
+
+/// Pinnacle touchpad modules supported by this driver, differing in sensor size
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Variant {
+    /// 40mm circular touchpad
+    #[default]
+    Tm040040,
+    /// 35mm circular touchpad
+    Tm035035,
+    /// 23mm circular touchpad
+    Tm023023,
+}
+
+impl Variant {
+    /// Diameter of the sensor area, in millimeters
+    pub fn diameter_mm(self) -> u8 {
+        match self {
+            Self::Tm040040 => 40,
+            Self::Tm035035 => 35,
+            Self::Tm023023 => 23,
+        }
+    }
+
+    /// Width and height of the sensor area, in millimeters
+    /// Both span the diameter, but the axes have different resolutions.
+    pub fn dimensions_mm(self) -> (u8, u8) {
+        (self.diameter_mm(), self.diameter_mm())
+    }
+
+    /// Absolute position counts per millimeter on the X axis
+    pub fn counts_per_mm(self) -> u16 {
+        (PINNACLE_X_UPPER - PINNACLE_X_LOWER) / self.diameter_mm() as u16
+    }
+
+    /// Absolute position counts per millimeter on the Y axis
+    pub fn y_counts_per_mm(self) -> u16 {
+        (PINNACLE_Y_UPPER - PINNACLE_Y_LOWER) / self.diameter_mm() as u16
+    }
+
+    /// Convert absolute counts on the X axis to micrometers
+    pub fn x_counts_to_um(self, counts: i32) -> i32 {
+        counts_to_um(
+            counts,
+            self.diameter_mm(),
+            PINNACLE_X_UPPER - PINNACLE_X_LOWER,
+        )
+    }
+
+    /// Convert absolute counts on the Y axis to micrometers
+    pub fn y_counts_to_um(self, counts: i32) -> i32 {
+        counts_to_um(
+            counts,
+            self.diameter_mm(),
+            PINNACLE_Y_UPPER - PINNACLE_Y_LOWER,
+        )
+    }
+
+    /// Absolute position in micrometers from the center of the sensor area, in sensor axes
+    pub fn position_um(self, x_pos: u16, y_pos: u16) -> (i32, i32) {
+        let x_center = (PINNACLE_X_LOWER + PINNACLE_X_UPPER) as i32 / 2;
+        let y_center = (PINNACLE_Y_LOWER + PINNACLE_Y_UPPER) as i32 / 2;
+
+        (
+            self.x_counts_to_um(x_pos as i32 - x_center),
+            self.y_counts_to_um(y_pos as i32 - y_center),
+        )
+    }
+}
+
+fn counts_to_um(counts: i32, diameter_mm: u8, range: u16) -> i32 {
+    (counts as i64 * diameter_mm as i64 * 1000 / range as i64) as i32
+}
+
+/// Speed in micrometers per second of a distance covered in `interval_us`
+pub(crate) fn um_per_s(distance_um: i32, interval_us: u32) -> i32 {
+    if interval_us == 0 {
+        return 0;
+    }
+
+    (distance_um as i64 * 1_000_000 / interval_us as i64) as i32
+}
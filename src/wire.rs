@@ -0,0 +1,404 @@
+//! Compact binary format of driver events, for streaming reports over UART or RTT to a host
+//! visualizer. Firmware encodes with `encode()`, host tools decode with `decode()`, both
+//! sharing this definition.
+//!
+//! Every frame starts with its length (including the length byte) and a tag, followed by the
+//! payload of the tag. Multi-byte values are little endian, report ages of `0xFFFF_FFFF`
+//! stand for no age.
+//!
+//! | Tag    | Event               | Payload                                                  |
+//! |--------|---------------------|----------------------------------------------------------|
+//! | `0x01` | Relative motion     | flags, X delta i16, Y delta i16, wheel i8, age, sequence |
+//! | `0x02` | Relative button     | like relative motion                                     |
+//! | `0x03` | Absolute report     | flags, buttons, X u16, Y u16, Z, filtered Z, confidence, radius u16, angle u16, X offset i16, Y offset i16, age, sequence |
+//! | `0x04` | Power mode          | mode                                                     |
+//! | `0x05` | Feed mode           | mode                                                     |
+//! | `0x06` | Position mode       | mode                                                     |
+//! | `0x07` | Orientation         | index of the `MountingOrientation` variant               |
+//! | `0x08` | Calibrated          |                                                          |
+//! | `0x09` | Suspended           |                                                          |
+//! | `0x0A` | Resumed             |                                                          |
+//! | `0x0B` | Data ready degraded |                                                          |
+//! | `0x0C` | Data ready restored |                                                          |
+//! | `0x0D` | Edge reached        | index of the `Edge` variant                              |
+//! | `0x0E` | Config drift        | drifted fields u32, see `ConfigMismatch::bits()`         |
+//!
+//! Relative flags: bit 0 to 2 primary, secondary and aux button, bit 3 stale, bit 4 and 5 the
+//! `XYEnable` value. Absolute flags: bit 0 multi-finger, bit 1 polar position present,
+//! bit 2 center offset present, bit 3 stale, bit 4 and 5 the `XYEnable` value.
+
+use crate::{
+    AbsoluteData, ConfigMismatch, Edge, Event, FeedMode, MountingOrientation, PolarPosition,
+    PositionMode, PowerMode, RelativeData, RelativeEvent, XYEnable,
+};
+
+/// Length of the longest frame
+pub const MAX_FRAME_LEN: usize = 27;
+
+const TAG_RELATIVE_MOTION: u8 = 0x01;
+const TAG_RELATIVE_BUTTON: u8 = 0x02;
+const TAG_ABSOLUTE: u8 = 0x03;
+const TAG_POWER_MODE: u8 = 0x04;
+const TAG_FEED_MODE: u8 = 0x05;
+const TAG_POSITION_MODE: u8 = 0x06;
+const TAG_ORIENTATION: u8 = 0x07;
+const TAG_CALIBRATED: u8 = 0x08;
+const TAG_SUSPENDED: u8 = 0x09;
+const TAG_RESUMED: u8 = 0x0A;
+const TAG_DATA_READY_DEGRADED: u8 = 0x0B;
+const TAG_DATA_READY_RESTORED: u8 = 0x0C;
+const TAG_EDGE_REACHED: u8 = 0x0D;
+const TAG_CONFIG_DRIFT: u8 = 0x0E;
+
+const FLAG_STALE: u8 = 0b0000_1000;
+const FLAG_MULTI_FINGER: u8 = 0b0000_0001;
+const FLAG_POLAR: u8 = 0b0000_0010;
+const FLAG_CENTER_OFFSET: u8 = 0b0000_0100;
+const AXES_SHIFT: u8 = 4;
+
+const NO_AGE: u32 = u32::MAX;
+
+/// Errors encoding or decoding frames
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WireError {
+    /// The buffer is too small for the frame
+    BufferTooSmall,
+    /// The bytes end before the frame does
+    Truncated,
+    /// The frame has an unknown tag
+    UnknownTag(u8),
+    /// The frame length or a value in the frame is invalid
+    InvalidFrame,
+}
+
+/// Encode an event into `buffer`, returning the length of the frame
+pub fn encode(event: &Event, buffer: &mut [u8]) -> Result<usize, WireError> {
+    let mut writer = Writer { buffer, len: 1 };
+    match event {
+        Event::Relative(event) => {
+            let (tag, data) = match event {
+                RelativeEvent::Motion(data) => (TAG_RELATIVE_MOTION, data),
+                RelativeEvent::ButtonPress(data) => (TAG_RELATIVE_BUTTON, data),
+            };
+            let buttons = data.primary_pressed as u8
+                | (data.secondary_pressed as u8) << 1
+                | (data.aux_pressed as u8) << 2;
+            writer.put(&[tag, buttons | flags(data.stale, data.axes)])?;
+            writer.put(&data.x_delta.to_le_bytes())?;
+            writer.put(&data.y_delta.to_le_bytes())?;
+            writer.put(&[data.wheel as u8])?;
+            writer.put(&data.age_us.unwrap_or(NO_AGE).to_le_bytes())?;
+            writer.put(&data.sequence.to_le_bytes())?;
+        }
+        Event::Absolute(data) => {
+            let mut flags = flags(data.stale, data.axes);
+            if data.multi_finger {
+                flags |= FLAG_MULTI_FINGER;
+            }
+            if data.polar.is_some() {
+                flags |= FLAG_POLAR;
+            }
+            if data.center_offset.is_some() {
+                flags |= FLAG_CENTER_OFFSET;
+            }
+            let polar = data.polar.unwrap_or_default();
+            let (x_offset, y_offset) = data.center_offset.unwrap_or_default();
+            writer.put(&[TAG_ABSOLUTE, flags, data.button_state])?;
+            writer.put(&data.x_pos.to_le_bytes())?;
+            writer.put(&data.y_pos.to_le_bytes())?;
+            writer.put(&[data.z_level, data.z_filtered, data.confidence])?;
+            writer.put(&polar.radius.to_le_bytes())?;
+            writer.put(&polar.angle.to_le_bytes())?;
+            writer.put(&x_offset.to_le_bytes())?;
+            writer.put(&y_offset.to_le_bytes())?;
+            writer.put(&data.age_us.unwrap_or(NO_AGE).to_le_bytes())?;
+            writer.put(&data.sequence.to_le_bytes())?;
+        }
+        Event::PowerMode(mode) => writer.put(&[TAG_POWER_MODE, *mode as u8])?,
+        Event::FeedMode(mode) => writer.put(&[TAG_FEED_MODE, *mode as u8])?,
+        Event::PositionMode(mode) => writer.put(&[TAG_POSITION_MODE, *mode as u8])?,
+        Event::Orientation(orientation) => writer.put(&[TAG_ORIENTATION, *orientation as u8])?,
+        Event::Calibrated => writer.put(&[TAG_CALIBRATED])?,
+        Event::Suspended => writer.put(&[TAG_SUSPENDED])?,
+        Event::Resumed => writer.put(&[TAG_RESUMED])?,
+        Event::DataReadyDegraded => writer.put(&[TAG_DATA_READY_DEGRADED])?,
+        Event::DataReadyRestored => writer.put(&[TAG_DATA_READY_RESTORED])?,
+        Event::EdgeReached(edge) => writer.put(&[TAG_EDGE_REACHED, *edge as u8])?,
+        Event::ConfigDrift(mismatch) => {
+            writer.put(&[TAG_CONFIG_DRIFT])?;
+            writer.put(&mismatch.bits().to_le_bytes())?;
+        }
+    }
+
+    let len = writer.len;
+    // Frames are at most `MAX_FRAME_LEN` long, the length always fits
+    writer.buffer[0] = len as u8;
+
+    Ok(len)
+}
+
+/// Decode the frame at the start of `bytes`, returning the event and the length of the frame
+pub fn decode(bytes: &[u8]) -> Result<(Event, usize), WireError> {
+    let len = *bytes.first().ok_or(WireError::Truncated)? as usize;
+    if len < 2 {
+        return Err(WireError::InvalidFrame);
+    }
+    let frame = bytes.get(1..len).ok_or(WireError::Truncated)?;
+    let mut reader = Reader { frame, pos: 1 };
+
+    let event = match frame[0] {
+        tag @ (TAG_RELATIVE_MOTION | TAG_RELATIVE_BUTTON) => {
+            let flags = reader.u8()?;
+            let data = RelativeData {
+                primary_pressed: flags & 0b001 != 0,
+                secondary_pressed: flags & 0b010 != 0,
+                aux_pressed: flags & 0b100 != 0,
+                x_delta: reader.u16()? as i16,
+                y_delta: reader.u16()? as i16,
+                wheel: reader.u8()? as i8,
+                axes: axes(flags)?,
+                age_us: reader.age()?,
+                stale: flags & FLAG_STALE != 0,
+                sequence: reader.u32()?,
+            };
+            Event::Relative(if tag == TAG_RELATIVE_MOTION {
+                RelativeEvent::Motion(data)
+            } else {
+                RelativeEvent::ButtonPress(data)
+            })
+        }
+        TAG_ABSOLUTE => {
+            let flags = reader.u8()?;
+            let button_state = reader.u8()?;
+            let x_pos = reader.u16()?;
+            let y_pos = reader.u16()?;
+            let z_level = reader.u8()?;
+            let z_filtered = reader.u8()?;
+            let confidence = reader.u8()?;
+            let polar = PolarPosition {
+                radius: reader.u16()?,
+                angle: reader.u16()?,
+            };
+            let center_offset = (reader.u16()? as i16, reader.u16()? as i16);
+            Event::Absolute(AbsoluteData {
+                button_state,
+                x_pos,
+                y_pos,
+                z_level,
+                z_filtered,
+                confidence,
+                axes: axes(flags)?,
+                multi_finger: flags & FLAG_MULTI_FINGER != 0,
+                polar: (flags & FLAG_POLAR != 0).then_some(polar),
+                center_offset: (flags & FLAG_CENTER_OFFSET != 0).then_some(center_offset),
+                age_us: reader.age()?,
+                stale: flags & FLAG_STALE != 0,
+                sequence: reader.u32()?,
+            })
+        }
+        TAG_POWER_MODE => Event::PowerMode(
+            PowerMode::try_from(reader.u8()?).map_err(|_| WireError::InvalidFrame)?,
+        ),
+        TAG_FEED_MODE => {
+            Event::FeedMode(FeedMode::try_from(reader.u8()?).map_err(|_| WireError::InvalidFrame)?)
+        }
+        TAG_POSITION_MODE => Event::PositionMode(
+            PositionMode::try_from(reader.u8()?).map_err(|_| WireError::InvalidFrame)?,
+        ),
+        TAG_ORIENTATION => Event::Orientation(
+            *MountingOrientation::ALL
+                .get(reader.u8()? as usize)
+                .ok_or(WireError::InvalidFrame)?,
+        ),
+        TAG_CALIBRATED => Event::Calibrated,
+        TAG_SUSPENDED => Event::Suspended,
+        TAG_RESUMED => Event::Resumed,
+        TAG_DATA_READY_DEGRADED => Event::DataReadyDegraded,
+        TAG_DATA_READY_RESTORED => Event::DataReadyRestored,
+        TAG_EDGE_REACHED => Event::EdgeReached(match reader.u8()? {
+            0 => Edge::Left,
+            1 => Edge::Right,
+            2 => Edge::Top,
+            3 => Edge::Bottom,
+            _ => return Err(WireError::InvalidFrame),
+        }),
+        TAG_CONFIG_DRIFT => Event::ConfigDrift(ConfigMismatch::from_bits(reader.u32()?)),
+        tag => return Err(WireError::UnknownTag(tag)),
+    };
+
+    if reader.pos != frame.len() {
+        return Err(WireError::InvalidFrame);
+    }
+
+    Ok((event, len))
+}
+
+fn flags(stale: bool, axes: XYEnable) -> u8 {
+    (stale as u8) << 3 | (axes as u8) << AXES_SHIFT
+}
+
+fn axes(flags: u8) -> Result<XYEnable, WireError> {
+    XYEnable::try_from((flags >> AXES_SHIFT) & 0b11).map_err(|_| WireError::InvalidFrame)
+}
+
+struct Writer<'b> {
+    buffer: &'b mut [u8],
+    len: usize,
+}
+
+impl Writer<'_> {
+    fn put(&mut self, bytes: &[u8]) -> Result<(), WireError> {
+        let end = self.len + bytes.len();
+        self.buffer
+            .get_mut(self.len..end)
+            .ok_or(WireError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.len = end;
+
+        Ok(())
+    }
+}
+
+struct Reader<'b> {
+    frame: &'b [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], WireError> {
+        let bytes = self
+            .frame
+            .get(self.pos..self.pos + N)
+            .ok_or(WireError::InvalidFrame)?;
+        self.pos += N;
+
+        // Can't fail, the slice is `N` long
+        Ok(bytes.try_into().unwrap_or([0; N]))
+    }
+
+    fn u8(&mut self) -> Result<u8, WireError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, WireError> {
+        Ok(u16::from_le_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, WireError> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn age(&mut self) -> Result<Option<u32>, WireError> {
+        let age = self.u32()?;
+
+        Ok((age != NO_AGE).then_some(age))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, encode, MAX_FRAME_LEN};
+    use crate::{
+        AbsoluteData, ConfigField, ConfigMismatch, Event, MountingOrientation, PolarPosition,
+        PowerMode, RelativeData, RelativeEvent, XYEnable,
+    };
+
+    fn round_trip(event: Event) -> Event {
+        let mut buffer = [0u8; MAX_FRAME_LEN];
+        let len = encode(&event, &mut buffer).unwrap();
+        let (decoded, decoded_len) = decode(&buffer[..len]).unwrap();
+        assert_eq!(decoded_len, len);
+
+        decoded
+    }
+
+    #[test]
+    fn round_trips_reports() {
+        let relative = RelativeData {
+            primary_pressed: true,
+            secondary_pressed: false,
+            aux_pressed: true,
+            x_delta: -200,
+            y_delta: 17,
+            wheel: -3,
+            axes: XYEnable::YDisabled,
+            age_us: Some(1500),
+            stale: true,
+            sequence: 0xDEAD_BEEF,
+        };
+        let Event::Relative(RelativeEvent::ButtonPress(decoded)) =
+            round_trip(Event::Relative(RelativeEvent::ButtonPress(relative)))
+        else {
+            panic!("wrong event");
+        };
+        assert_eq!(
+            (decoded.x_delta, decoded.y_delta, decoded.wheel),
+            (-200, 17, -3)
+        );
+        assert!(decoded.primary_pressed && !decoded.secondary_pressed && decoded.aux_pressed);
+        assert_eq!(decoded.axes, XYEnable::YDisabled);
+        assert_eq!((decoded.age_us, decoded.stale), (Some(1500), true));
+        assert_eq!(decoded.sequence, 0xDEAD_BEEF);
+
+        let absolute = AbsoluteData {
+            button_state: 0x03,
+            x_pos: 1920,
+            y_pos: 64,
+            z_level: 42,
+            z_filtered: 38,
+            confidence: 85,
+            multi_finger: true,
+            polar: Some(PolarPosition {
+                radius: 896,
+                angle: 49152,
+            }),
+            center_offset: Some((-960, 512)),
+            sequence: 7,
+            ..Default::default()
+        };
+        let Event::Absolute(decoded) = round_trip(Event::Absolute(absolute)) else {
+            panic!("wrong event");
+        };
+        assert_eq!(
+            (
+                decoded.button_state,
+                decoded.x_pos,
+                decoded.y_pos,
+                decoded.z_level,
+                decoded.z_filtered,
+                decoded.confidence
+            ),
+            (0x03, 1920, 64, 42, 38, 85)
+        );
+        assert!(decoded.multi_finger && !decoded.stale);
+        assert_eq!(decoded.polar, absolute.polar);
+        assert_eq!(decoded.center_offset, absolute.center_offset);
+        assert_eq!((decoded.age_us, decoded.sequence), (None, 7));
+    }
+
+    #[test]
+    fn round_trips_state_changes() {
+        assert!(matches!(
+            round_trip(Event::PowerMode(PowerMode::Sleep)),
+            Event::PowerMode(PowerMode::Sleep)
+        ));
+        assert!(matches!(
+            round_trip(Event::Orientation(MountingOrientation::AntiTranspose)),
+            Event::Orientation(MountingOrientation::AntiTranspose)
+        ));
+        assert!(matches!(round_trip(Event::Resumed), Event::Resumed));
+        let drift = ConfigMismatch::from_bits(1 << ConfigField::XYInverted as u32);
+        assert_eq!(
+            round_trip(Event::ConfigDrift(drift)),
+            Event::ConfigDrift(drift)
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_frames() {
+        let mut buffer = [0u8; MAX_FRAME_LEN];
+        let len = encode(&Event::Absolute(AbsoluteData::default()), &mut buffer).unwrap();
+        assert_eq!(len, MAX_FRAME_LEN);
+        assert!(decode(&buffer[..len - 1]).is_err());
+    }
+}
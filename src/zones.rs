@@ -0,0 +1,173 @@
+use heapless::Vec;
+
+use crate::Gesture;
+
+/// A rectangular region of the touchpad, in output coordinates (bounds inclusive)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// Lowest X position in the region
+    pub x_min: u16,
+    /// Lowest Y position in the region
+    pub y_min: u16,
+    /// Highest X position in the region
+    pub x_max: u16,
+    /// Highest Y position in the region
+    pub y_max: u16,
+}
+
+impl Region {
+    /// Create a region
+    pub const fn new(x_min: u16, y_min: u16, x_max: u16, y_max: u16) -> Self {
+        Self {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+        }
+    }
+
+    /// Whether the position is inside the region
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+}
+
+/// A rectangular tap region mapped to a key, in output coordinates (bounds inclusive)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TapZone<K> {
+    /// Lowest X position in the zone
+    pub x_min: u16,
+    /// Lowest Y position in the zone
+    pub y_min: u16,
+    /// Highest X position in the zone
+    pub x_max: u16,
+    /// Highest Y position in the zone
+    pub y_max: u16,
+    /// Key emitted for taps in the zone, e.g. a HID keycode
+    pub key: K,
+}
+
+impl<K> TapZone<K> {
+    /// Create a tap zone
+    pub const fn new(x_min: u16, y_min: u16, x_max: u16, y_max: u16, key: K) -> Self {
+        Self {
+            x_min,
+            y_min,
+            x_max,
+            y_max,
+            key,
+        }
+    }
+
+    /// Whether the position is inside the zone
+    pub fn contains(&self, x: u16, y: u16) -> bool {
+        (self.x_min..=self.x_max).contains(&x) && (self.y_min..=self.y_max).contains(&y)
+    }
+}
+
+/// Timing of the tap zone mapping
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ZoneConfig {
+    /// Maximum time between two taps in different zones forming a chord, in milliseconds
+    /// Single taps are delayed by this long, 0 disables chords.
+    pub chord_window_ms: u32,
+}
+
+impl Default for ZoneConfig {
+    fn default() -> Self {
+        Self {
+            chord_window_ms: 150,
+        }
+    }
+}
+
+/// Keys produced by `TapZones`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ZoneEvent<K> {
+    /// A single tap in the zone with this key
+    Tap(K),
+    /// Taps in two zones in rapid succession, with the keys in tap order
+    Chord(K, K),
+}
+
+/// Maps taps detected by the `GestureEngine` to keys of up to `N` zones, e.g. for a touchpad
+/// used as the only input device of a wearable.
+///
+/// Taps in two different zones within the chord window emit a chord instead of two taps.
+/// Single taps are emitted once the window expired, so `update()` is also called when no
+/// gesture was detected. Taps outside of all zones are ignored, zones are hit-tested in the
+/// order they were added.
+#[derive(Clone, Debug)]
+pub struct TapZones<K, const N: usize> {
+    config: ZoneConfig,
+    zones: Vec<TapZone<K>, N>,
+    /// Time and index of the zone of a tap waiting for a second one
+    pending: Option<(u64, usize)>,
+}
+
+impl<K: Copy, const N: usize> TapZones<K, N> {
+    /// Create a mapping without zones
+    pub fn new(config: ZoneConfig) -> Self {
+        Self {
+            config,
+            zones: Vec::new(),
+            pending: None,
+        }
+    }
+
+    /// Get the configured timing
+    pub fn config(&self) -> &ZoneConfig {
+        &self.config
+    }
+
+    /// Add a zone, returning it back if there are already `N`
+    pub fn add_zone(&mut self, zone: TapZone<K>) -> Result<(), TapZone<K>> {
+        self.zones.push(zone)
+    }
+
+    /// Get the zone containing a position
+    pub fn zone_at(&self, x: u16, y: u16) -> Option<&TapZone<K>> {
+        self.zones.iter().find(|zone| zone.contains(x, y))
+    }
+
+    /// Process the result of the gesture engine at `timestamp_us`, returning the key event to
+    /// emit, if any
+    pub fn update(&mut self, timestamp_us: u64, gesture: Option<Gesture>) -> Option<ZoneEvent<K>> {
+        let window_us = self.config.chord_window_ms as u64 * 1000;
+        let tapped = match gesture {
+            Some(Gesture::Tap(location)) => self
+                .zones
+                .iter()
+                .position(|zone| zone.contains(location.x, location.y)),
+            _ => None,
+        };
+
+        let Some(zone) = tapped else {
+            // Emit a single tap once it can't become a chord anymore
+            let (tap_us, pending) = self.pending?;
+            if timestamp_us.saturating_sub(tap_us) < window_us {
+                return None;
+            }
+            self.pending = None;
+            return Some(ZoneEvent::Tap(self.zones[pending].key));
+        };
+        if window_us == 0 {
+            return Some(ZoneEvent::Tap(self.zones[zone].key));
+        }
+
+        match self.pending.replace((timestamp_us, zone)) {
+            None => None,
+            Some((tap_us, pending))
+                if pending != zone && timestamp_us.saturating_sub(tap_us) < window_us =>
+            {
+                self.pending = None;
+                Some(ZoneEvent::Chord(
+                    self.zones[pending].key,
+                    self.zones[zone].key,
+                ))
+            }
+            // The new tap waits for a chord in turn
+            Some((_, pending)) => Some(ZoneEvent::Tap(self.zones[pending].key)),
+        }
+    }
+}